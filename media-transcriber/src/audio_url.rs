@@ -0,0 +1,217 @@
+use anyhow::Result;
+use log::{debug, error, info, warn};
+use percent_encoding::percent_decode_str;
+use reqwest::header::CONTENT_TYPE;
+use std::fs;
+use std::time::Duration;
+use tempfile::tempdir;
+
+use crate::config::Config;
+use crate::embed;
+use crate::listing::ListedItem;
+use crate::manifest::{Manifest, SourceKind};
+use crate::template::{ItemNaming, ItemPaths, AUDIO_FILE_NAME, ERROR_FILE_NAME};
+use crate::transcription::TranscriptionService;
+use crate::utils;
+
+/// Directory that audio URLs' transcripts go under
+const AUDIO_URLS_DIR: &str = "audio_urls";
+
+/// What `{channel}` resolves to for audio URLs in an output template
+const AUDIO_URLS_CHANNEL: &str = "Audio URLs";
+
+/// How long to wait for the HEAD request that checks whether a URL is audio
+const AUDIO_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Processor for URLs pointing directly at an audio file, outside any feed
+pub struct AudioUrlProcessor<'a> {
+    /// Configuration for the processor
+    config: &'a Config,
+}
+
+impl<'a> AudioUrlProcessor<'a> {
+    /// Create a new audio URL processor
+    pub fn new(config: &'a Config) -> Self {
+        Self { config }
+    }
+
+    /// Check if a URL points directly at audio rather than at a feed
+    /// 
+    /// URLs ending in a known audio extension are audio. Otherwise a HEAD
+    /// request decides by its `Content-Type`; if that fails, the URL is
+    /// assumed to be a feed.
+    pub async fn is_audio_url(url: &str, config: &Config) -> bool {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return false;
+        }
+        if utils::has_audio_extension(url) {
+            return true;
+        }
+
+        let response = config.http_client.head(url).timeout(AUDIO_CHECK_TIMEOUT).send().await;
+        match response {
+            Ok(response) => response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|content_type| content_type.trim().to_lowercase().starts_with("audio/")),
+            Err(e) => {
+                debug!("Couldn't check the content type of {}: {}", url, e);
+                false
+            }
+        }
+    }
+
+    /// Download and transcribe the audio a URL points at
+    /// 
+    /// The output directory is named after the URL's file name.
+    pub async fn process(&self, url: &str) -> Result<()> {
+        info!("Detected audio URL: {}", url);
+        let title = file_stem(url);
+
+        // Estimate cost and confirm before downloading
+        let duration = utils::probe_duration(&*self.config.command_runner, url).ok();
+        if !utils::confirm_estimated_cost(self.config, &[duration])? {
+            return Ok(());
+        }
+
+        // Create output directory
+        let naming = ItemNaming {
+            channel: AUDIO_URLS_CHANNEL,
+            title: &title,
+            id: &title,
+            date: None,
+        };
+        let paths = self.config.claim_item_paths(
+            &utils::sanitize_filename(&title),
+            &naming,
+            None,
+            |dir_name, naming| match &self.config.output_template {
+                Some(template) => template.item_paths(&self.config.output_dir, naming, self.config.format, "audio_info.txt"),
+                None => {
+                    let dir = utils::item_dir(&self.config.output_dir.join(AUDIO_URLS_DIR), dir_name);
+                    ItemPaths {
+                        transcript: dir.join(self.config.format.transcript_file_name()),
+                        info: dir.join("audio_info.txt"),
+                        audio: dir.join(AUDIO_FILE_NAME),
+                        error: dir.join(ERROR_FILE_NAME),
+                        dir,
+                    }
+                }
+            },
+            |info| utils::info_field(info, "Audio URL").is_some_and(|existing| existing != url),
+        );
+        let Some(paths) = paths else {
+            return Ok(());
+        };
+        fs::create_dir_all(&paths.dir)?;
+
+        // Save audio info
+        let audio_info = format!(
+            "Title: {}\nAudio URL: {}\nTranscribed: {}\n",
+            title,
+            url,
+            chrono::Local::now().to_rfc3339()
+        );
+        fs::write(&paths.info, audio_info)?;
+
+        let transcription_service = TranscriptionService::new(self.config);
+        let mut manifest = Manifest::new(self.config, SourceKind::AudioUrl, &title, url, self.config.language.clone());
+
+        if self.config.dry_run {
+            info!("[dry run] Would transcribe: {} (duration: {})", url, utils::format_duration(duration));
+            manifest.skipped(&title, url, duration, "dry run");
+            return manifest.write(&paths.dir);
+        }
+
+        if self.config.detect_language_only {
+            let result = transcription_service.report_language(&title, url, &paths.info).await;
+            match &result {
+                Ok(()) => manifest.skipped(&title, url, duration, "language detection only"),
+                Err(e) => manifest.failed(&title, url, duration, e),
+            }
+            manifest.write(&paths.dir)?;
+            return result;
+        }
+
+        // Download the audio, unless it's already cached, and transcribe it
+        let deadline = utils::ItemDeadline::start(self.config);
+        let temp_dir = tempdir()?;
+        let result = deadline
+            .run(async {
+                let audio_file = match utils::cached_audio(self.config, url) {
+                    Some(cached) => cached,
+                    None => {
+                        let audio_file = temp_dir.path().join(format!("audio.{}", utils::audio_extension(url)));
+                        utils::download_file(url, &audio_file, self.config).await?;
+                        utils::cache_audio(self.config, url, &audio_file);
+                        audio_file
+                    }
+                };
+                utils::keep_audio(self.config, &audio_file, &paths.audio.with_extension(utils::audio_extension(url)));
+                transcription_service.transcribe_file(&audio_file, &paths.transcript, &title, url).await
+            })
+            .await;
+        match &result {
+            Ok(()) => {
+                embed::embed_transcript(
+                    self.config,
+                    &paths.audio.with_extension(utils::audio_extension(url)),
+                    &transcription_service.transcript_path(&paths.transcript),
+                    &[],
+                );
+                manifest.success(&title, url, &transcription_service.transcript_path(&paths.transcript), duration);
+                paths.clear_failure();
+            }
+            Err(e) if e.is::<utils::TooLarge>() => {
+                warn!("Skipping {}: {}", url, e);
+                manifest.skipped(&title, url, duration, "larger than --max-filesize");
+                return manifest.write(&paths.dir);
+            }
+            Err(e) => {
+                error!("Failed to transcribe audio URL: {}", e);
+                manifest.failed(&title, url, duration, e);
+                paths.record_failure(e);
+            }
+        }
+        manifest.write(&paths.dir)?;
+        result?;
+
+        transcription_service.record_detection(&paths.info, &title)?;
+
+        info!("Transcription complete: {:?}", paths.transcript);
+        Ok(())
+    }
+
+    /// The audio URL as a single listed item
+    pub fn list(&self, url: &str) -> ListedItem {
+        ListedItem {
+            source: url.to_string(),
+            title: file_stem(url),
+            date: None,
+            duration: utils::probe_duration(&*self.config.command_runner, url).ok(),
+            url: url.to_string(),
+        }
+    }
+}
+
+/// Name of the file a URL points at, without its extension, e.g. `My Episode` for `.../My%20Episode.mp3`
+/// 
+/// Falls back to the host name for URLs without a file name.
+fn file_stem(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+
+    let file_name = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+        .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned());
+    match file_name {
+        Some(file_name) => match file_name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => stem.to_string(),
+            _ => file_name,
+        },
+        None => parsed.host_str().unwrap_or("audio").to_string(),
+    }
+}
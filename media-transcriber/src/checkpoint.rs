@@ -0,0 +1,83 @@
+use anyhow::Result;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File in the output directory recording which sources of a `--file` run finished
+pub const CHECKPOINT_FILE: &str = "sources_checkpoint.json";
+
+/// Sources of a `--file` run that finished without failures, so `--resume` can skip them
+/// 
+/// Saved after every finished source. Each run without `--resume` starts a
+/// new checkpoint, so only sources finished since the last fresh start count.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourcesCheckpoint {
+    /// Sources file the run was processing
+    sources_file: PathBuf,
+    /// Sources that finished, in the order they finished
+    completed: Vec<String>,
+    /// Where the checkpoint is saved
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl SourcesCheckpoint {
+    /// Start a checkpoint for `sources_file` in `output_dir`
+    /// 
+    /// With `resume`, picks up the previous run's checkpoint, unless it was
+    /// for a different sources file.
+    pub fn open(output_dir: &Path, sources_file: &Path, resume: bool) -> Result<Self> {
+        let path = output_dir.join(CHECKPOINT_FILE);
+        let sources_file = fs::canonicalize(sources_file).unwrap_or_else(|_| sources_file.to_path_buf());
+        let fresh = Self {
+            sources_file: sources_file.clone(),
+            completed: Vec::new(),
+            path: path.clone(),
+        };
+        if !resume {
+            return Ok(fresh);
+        }
+        
+        let previous = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str::<Self>(&content)?,
+            Err(_) => {
+                info!("No checkpoint found at {:?}, processing all sources", path);
+                return Ok(fresh);
+            }
+        };
+        if previous.sources_file != sources_file {
+            warn!(
+                "Checkpoint at {:?} is for {:?}, not {:?}; processing all sources",
+                path, previous.sources_file, sources_file
+            );
+            return Ok(fresh);
+        }
+        
+        info!("Resuming: {} sources already finished", previous.completed.len());
+        Ok(Self { path, ..previous })
+    }
+    
+    /// Whether a source finished in the run being resumed (or earlier in this one)
+    pub fn is_completed(&self, source: &str) -> bool {
+        self.completed.iter().any(|completed| completed == source)
+    }
+    
+    /// Record a finished source and save the checkpoint
+    /// 
+    /// The checkpoint is replaced in one step, so a crash never leaves it half-written.
+    pub fn complete(&mut self, source: &str) -> Result<()> {
+        if !self.is_completed(source) {
+            self.completed.push(source.to_string());
+        }
+        
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp_path, &self.path)?;
+        debug!("Saved checkpoint to: {:?}", self.path);
+        Ok(())
+    }
+}
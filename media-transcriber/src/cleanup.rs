@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use crate::json::JsonTranscript;
+
+/// Built-in filler words by language: code, name (as OpenAI reports detected languages), and words
+const DEFAULT_FILLER_WORDS: &[(&str, &str, &[&str])] = &[
+    ("en", "english", &["um", "umm", "uh", "uhh", "uhm", "er", "erm", "hmm", "mm"]),
+    ("de", "german", &["äh", "ähm", "öh", "hm"]),
+    ("es", "spanish", &["eh", "em", "mmm"]),
+    ("fr", "french", &["euh", "heu"]),
+    ("it", "italian", &["ehm", "eh", "mmm"]),
+    ("nl", "dutch", &["eh", "uh", "ehm"]),
+    ("pt", "portuguese", &["hã", "ahn", "éh"]),
+];
+
+/// Tidies transcript text for `--clean`
+/// 
+/// Filler words are removed, immediately repeated words ("the the") are
+/// collapsed, and whitespace is normalized. Words starting a sentence are
+/// capitalized, including ones that follow a removed filler, and so is
+/// English "i".
+pub struct Cleaner {
+    fillers: Vec<String>,
+    english: bool,
+}
+
+impl Cleaner {
+    /// Cleaner for transcripts in `language`, a code like `en` or a name like `english` (English when unknown)
+    /// 
+    /// A list in `custom` for the language, keyed by code, replaces the built-in one.
+    pub fn new(language: Option<&str>, custom: &HashMap<String, Vec<String>>) -> Self {
+        let language = language
+            .and_then(|language| language.trim().split(['-', '_']).next())
+            .filter(|language| !language.is_empty())
+            .unwrap_or("en")
+            .to_lowercase();
+        let builtin = DEFAULT_FILLER_WORDS
+            .iter()
+            .find(|(code, name, _)| *code == language || *name == language);
+        let code = builtin.map_or(language.as_str(), |(code, _, _)| code);
+        
+        let fillers = match custom.iter().find(|(key, _)| key.eq_ignore_ascii_case(code)) {
+            Some((_, words)) => words.iter().map(|word| word.trim().to_lowercase()).collect(),
+            None => builtin.map_or_else(Vec::new, |(_, _, words)| words.iter().map(|word| word.to_string()).collect()),
+        };
+        
+        Self {
+            fillers,
+            english: code == "en",
+        }
+    }
+    
+    /// Clean a transcript's text and segments, keeping segments' leading voice tags, and drop
+    /// filler words along with segments that have nothing left
+    pub fn clean(&self, transcript: &mut JsonTranscript) {
+        transcript.text = self.clean_text(&transcript.text);
+        for segment in &mut transcript.segments {
+            let (voice, text) = split_voice_tag(&segment.text);
+            let text = self.clean_text(text);
+            segment.text = if text.trim().is_empty() { String::new() } else { format!("{}{}", voice, text) };
+        }
+        transcript.segments.retain(|segment| !segment.text.is_empty());
+        transcript.words.retain(|word| !self.is_filler(&word_key(&word.word)));
+    }
+    
+    /// Clean plain text line by line, leaving `## ` chapter headings alone
+    fn clean_text(&self, text: &str) -> String {
+        text.lines()
+            .map(|line| if line.starts_with("## ") { line.to_string() } else { self.clean_line(line) })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    
+    /// Clean one line of text
+    fn clean_line(&self, line: &str) -> String {
+        let mut kept: Vec<String> = Vec::new();
+        let mut capitalize_next = false;
+        
+        for token in line.split_whitespace() {
+            let (word, punctuation) = split_punctuation(token);
+            let key = word_key(word);
+            
+            if self.is_filler(&key) {
+                // The filler may have started or ended the sentence, which its neighbours take over;
+                // one set off by commas ("is, uh, simple") takes the comma before it along
+                capitalize_next |= word.chars().find(|c| c.is_alphabetic()).is_some_and(char::is_uppercase);
+                if let Some(last) = kept.last_mut() {
+                    let (last_word, last_punctuation) = split_punctuation(last);
+                    if ends_sentence(punctuation) {
+                        *last = format!("{}{}", last_word, punctuation);
+                    } else if punctuation == "," && last_punctuation == "," {
+                        *last = last_word.to_string();
+                    }
+                }
+                continue;
+            }
+            
+            if let Some(last) = kept.last_mut() {
+                let (last_word, _) = split_punctuation(last);
+                if !key.is_empty() && word_key(last_word) == key && key.chars().all(char::is_alphabetic) {
+                    *last = format!("{}{}", last_word, punctuation);
+                    continue;
+                }
+            }
+            
+            let starts_sentence = capitalize_next || kept.last().is_some_and(|last| ends_sentence(split_punctuation(last).1));
+            let is_english_i = self.english && (key == "i" || key.starts_with("i'") || key.starts_with("i’"));
+            kept.push(if starts_sentence || is_english_i { capitalize(token) } else { token.to_string() });
+            capitalize_next = false;
+        }
+        
+        kept.join(" ")
+    }
+    
+    fn is_filler(&self, key: &str) -> bool {
+        self.fillers.iter().any(|filler| filler == key)
+    }
+}
+
+/// Split a token into the word and its trailing punctuation, e.g. `so,` -> (`so`, `,`)
+fn split_punctuation(token: &str) -> (&str, &str) {
+    let end = token
+        .char_indices()
+        .rfind(|(_, c)| c.is_alphanumeric())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    token.split_at(end)
+}
+
+/// A word in lowercase without leading punctuation such as quotes, for comparisons
+fn word_key(word: &str) -> String {
+    let (word, _) = split_punctuation(word.trim());
+    word.trim_start_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+/// Whether trailing punctuation ends a sentence
+fn ends_sentence(punctuation: &str) -> bool {
+    punctuation.contains(['.', '?', '!'])
+}
+
+/// Uppercase a token's first letter, after any leading punctuation
+fn capitalize(token: &str) -> String {
+    match token.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, c)) => format!("{}{}{}", &token[..i], c.to_uppercase(), &token[i + c.len_utf8()..]),
+        None => token.to_string(),
+    }
+}
+
+/// Split a leading WebVTT voice tag like `<v Speaker 1>` off cue text
+fn split_voice_tag(text: &str) -> (&str, &str) {
+    match text.find('>') {
+        Some(end) if text.starts_with("<v") => text.split_at(end + 1),
+        _ => ("", text),
+    }
+}
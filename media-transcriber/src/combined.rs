@@ -0,0 +1,112 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use log::{info, warn};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::OutputFormat;
+use crate::json::JsonTranscript;
+use crate::{html, markdown, srt, vtt};
+
+/// File name of the combined transcript at a podcast's or channel's root
+const COMBINED_FILE_NAME: &str = "full_transcript.md";
+
+/// Every transcript of a podcast or channel in one Markdown file, for full-text search
+pub struct CombinedTranscript {
+    /// Podcast or channel name, used as the top-level heading
+    title: String,
+    format: OutputFormat,
+    entries: Vec<Entry>,
+}
+
+/// A transcribed episode/video
+struct Entry {
+    title: String,
+    date: Option<NaiveDate>,
+    transcript_file: PathBuf,
+}
+
+impl CombinedTranscript {
+    /// Start a combined transcript for transcripts written in `format`
+    pub fn new(title: &str, format: OutputFormat) -> Self {
+        Self {
+            title: title.to_string(),
+            format,
+            entries: Vec::new(),
+        }
+    }
+    
+    /// Add an item whose transcript has been written to `transcript_file`
+    pub fn add(&mut self, title: &str, date: Option<NaiveDate>, transcript_file: &Path) {
+        self.entries.push(Entry {
+            title: title.to_string(),
+            date,
+            transcript_file: transcript_file.to_path_buf(),
+        });
+    }
+    
+    /// Write `full_transcript.md` in `dir`, oldest item first and undated items last
+    /// 
+    /// SRT and VTT transcripts are reduced to their text. Nothing is written if
+    /// no items were added.
+    pub fn write(mut self, dir: &Path) -> Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        
+        self.entries.sort_by_key(|entry| (entry.date.is_none(), entry.date));
+        
+        let mut output = format!("# {}\n", self.title);
+        for entry in &self.entries {
+            let text = fs::read_to_string(&entry.transcript_file)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| plain_text(&content, self.format));
+            let text = match text {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!("Leaving '{}' out of the combined transcript: {}", entry.title, e);
+                    continue;
+                }
+            };
+            
+            output.push_str(&format!("\n## {}\n\n", entry.title));
+            if let Some(date) = entry.date {
+                output.push_str(&format!("*{}*\n\n", date));
+            }
+            output.push_str(text.trim());
+            output.push('\n');
+        }
+        
+        let combined_file = dir.join(COMBINED_FILE_NAME);
+        fs::write(&combined_file, output)?;
+        info!("Combined transcript saved to: {:?}", combined_file);
+        Ok(())
+    }
+}
+
+/// Transcript text without SRT/VTT numbering and timestamps
+/// 
+/// VTT voice spans become `Speaker: ` prefixes.
+pub fn plain_text(content: &str, format: OutputFormat) -> Result<String> {
+    let cues = match format {
+        OutputFormat::Txt => return Ok(content.to_string()),
+        OutputFormat::Srt => srt::parse(content)?,
+        OutputFormat::Vtt => vtt::parse(content)?,
+        OutputFormat::Html => return Ok(html::plain_text(content)),
+        OutputFormat::Md => return Ok(markdown::plain_text(content)),
+        OutputFormat::Json | OutputFormat::All => return Ok(JsonTranscript::parse(content)?.text),
+    };
+    
+    let voice = Regex::new(r"<v(?:\.[^ >]*)? ([^>]*)>").unwrap();
+    let tag = Regex::new(r"</?[^>]+>").unwrap();
+    
+    let lines: Vec<String> = cues
+        .iter()
+        .map(|cue| {
+            let text = voice.replace_all(&cue.text, "$1: ");
+            tag.replace_all(&text, "").replace('\n', " ")
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
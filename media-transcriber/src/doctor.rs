@@ -0,0 +1,121 @@
+use colored::Colorize;
+use std::io;
+use std::process::Command;
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, ConfigFile, Engine};
+use crate::utils;
+
+/// An external program the tool calls
+struct Tool {
+    name: &'static str,
+    command: PathBuf,
+    /// Flag that makes the program print its version
+    version_flag: &'static str,
+    /// When the program is needed, for the "not found" message
+    needed_for: &'static str,
+    /// Whether a missing program makes the check fail
+    required: bool,
+}
+
+/// Print the versions of the external tools and whether API keys are set up, for bug reports
+/// 
+/// Returns `false` if a required tool is missing. podscript is only required
+/// when the configured engine is OpenAI.
+pub fn run(api_key: Option<String>, engine: Option<Engine>, podscript_bin: Option<&Path>, file: &ConfigFile) -> bool {
+    let engine = engine.or(file.engine).unwrap_or(Engine::OpenAi);
+    let podscript = config::find_podscript_binary(podscript_bin).unwrap_or_else(|_| {
+        podscript_bin.map_or_else(|| PathBuf::from(config::DEFAULT_PODSCRIPT_BINARY), Path::to_path_buf)
+    });
+    let tools = [
+        Tool {
+            name: "yt-dlp",
+            command: PathBuf::from("yt-dlp"),
+            version_flag: "--version",
+            needed_for: "YouTube sources",
+            required: true,
+        },
+        Tool {
+            name: "ffmpeg",
+            command: PathBuf::from("ffmpeg"),
+            version_flag: "-version",
+            needed_for: "converting and splitting audio",
+            required: true,
+        },
+        Tool {
+            name: "ffprobe",
+            command: PathBuf::from("ffprobe"),
+            version_flag: "-version",
+            needed_for: "reading audio durations",
+            required: true,
+        },
+        Tool {
+            name: "podscript",
+            command: podscript,
+            version_flag: "--version",
+            needed_for: "the openai engine",
+            required: engine == Engine::OpenAi,
+        },
+    ];
+    
+    println!("{} {}", "media-transcriber".bold(), env!("CARGO_PKG_VERSION"));
+    println!("Engine: {:?}", engine);
+    println!();
+    
+    let mut all_found = true;
+    for tool in &tools {
+        match tool_version(tool) {
+            Ok(version) => println!("{:<20}{} {}", tool.name, "✓".green(), version),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mark = if tool.required { "✗".red() } else { "-".yellow() };
+                println!("{:<20}{} not found (needed for {})", tool.name, mark, tool.needed_for);
+                all_found &= !tool.required;
+            }
+            Err(e) => {
+                println!("{:<20}{} could not be run: {}", tool.name, "✗".red(), e);
+                all_found &= !tool.required;
+            }
+        }
+    }
+    
+    println!();
+    let openai_key = config::find_api_key(api_key, file);
+    let openai_status = match openai_key.as_deref() {
+        Some(key) if key.starts_with("sk-") => "configured".green(),
+        Some(_) => "set, but doesn't look like an OpenAI key (expected sk-...)".yellow(),
+        None => "not configured".yellow(),
+    };
+    println!("{:<20}{}", "OpenAI API key", openai_status);
+    
+    let assemblyai_status = match config::find_assemblyai_api_key() {
+        Some(_) => "configured".green(),
+        None => "not configured".yellow(),
+    };
+    println!("{:<20}{}", "AssemblyAI API key", assemblyai_status);
+    
+    all_found
+}
+
+/// Version reported by a tool, from the first line of its version output
+/// 
+/// `ffmpeg version 6.1.1 Copyright ...` gives `6.1.1`; output without a
+/// `version` word is shown as it is.
+fn tool_version(tool: &Tool) -> io::Result<String> {
+    let output = utils::command_output(Command::new(&tool.command).arg(tool.version_flag))?;
+    let text = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+    let text = String::from_utf8_lossy(&text);
+    let first_line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("");
+    
+    let mut words = first_line.split_whitespace();
+    let version = match words.position(|word| word.eq_ignore_ascii_case("version")) {
+        Some(_) => words.next().unwrap_or(first_line),
+        None => first_line,
+    };
+    
+    if version.is_empty() {
+        Ok("found (version unknown)".to_string())
+    } else {
+        Ok(version.to_string())
+    }
+}
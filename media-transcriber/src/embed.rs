@@ -0,0 +1,148 @@
+use anyhow::Result;
+use log::{debug, info, warn};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+use crate::combined;
+use crate::config::{Config, OutputFormat};
+use crate::json::JsonTranscript;
+use crate::srt::{self, Cue};
+use crate::utils::Chapter;
+use crate::vtt;
+
+/// How a container holds a transcript
+enum Embedding {
+    /// As a subtitle track with the given codec, when the transcript has timestamps
+    Subtitles(&'static str),
+    /// Only as a `lyrics` tag
+    Lyrics,
+}
+
+/// How a kept audio file's container can hold a transcript, by its extension
+fn embedding_for(extension: &str) -> Option<Embedding> {
+    match extension {
+        "m4a" | "m4b" | "mp4" | "mov" => Some(Embedding::Subtitles("mov_text")),
+        "mkv" | "mka" => Some(Embedding::Subtitles("srt")),
+        "mp3" => Some(Embedding::Lyrics),
+        _ => None,
+    }
+}
+
+/// Attach a transcript (and chapters, if known) to kept audio, if `--embed-transcript` is set
+/// 
+/// Timed transcripts become a subtitle track in containers that have them
+/// (m4a/mp4 and mkv); otherwise the text goes into a `lyrics` tag. The audio
+/// is remuxed into a copy that then replaces it, so a failed run leaves the
+/// kept audio untouched. Failing to embed only logs a warning.
+pub fn embed_transcript(config: &Config, audio: &Path, transcript: &Path, chapters: &[Chapter]) {
+    if !config.embed_transcript {
+        return;
+    }
+    
+    if let Err(e) = embed(config, audio, transcript, chapters) {
+        warn!("Failed to embed the transcript in {:?}: {}", audio, e);
+    }
+}
+
+fn embed(config: &Config, audio: &Path, transcript: &Path, chapters: &[Chapter]) -> Result<()> {
+    let extension = audio
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let Some(embedding) = embedding_for(&extension) else {
+        warn!(
+            "Can't embed the transcript in {:?}: .{} files aren't supported (only m4a, mp4, mkv, and mp3 are)",
+            audio, extension
+        );
+        return Ok(());
+    };
+    
+    let content = fs::read_to_string(transcript)?;
+    let cues = match embedding {
+        Embedding::Subtitles(_) => timed_cues(&content, config.format)?,
+        Embedding::Lyrics => None,
+    };
+    
+    let temp_dir = tempdir()?;
+    let audio_arg = audio.to_str().unwrap();
+    
+    // Start from the file's own tags, so they survive the remux
+    let metadata_file = temp_dir.path().join("metadata.txt");
+    config.command_runner.run(
+        "ffmpeg",
+        &["-nostdin", "-v", "quiet", "-y", "-i", audio_arg, "-f", "ffmetadata", metadata_file.to_str().unwrap()],
+    )?;
+    let mut metadata = fs::read_to_string(&metadata_file)?;
+    if cues.is_none() {
+        metadata.push_str(&format!("lyrics={}\n", escape_metadata(&combined::plain_text(&content, config.format)?)));
+    }
+    for chapter in chapters {
+        metadata.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\n",
+            (chapter.start * 1000.0) as u64,
+            (chapter.end * 1000.0) as u64
+        ));
+        if let Some(title) = &chapter.title {
+            metadata.push_str(&format!("title={}\n", escape_metadata(title)));
+        }
+    }
+    fs::write(&metadata_file, metadata)?;
+    
+    let mut args = vec![
+        "-nostdin", "-v", "quiet", "-y",
+        "-i", audio_arg,
+        "-i", metadata_file.to_str().unwrap(),
+    ];
+    
+    let subtitles_file = temp_dir.path().join("transcript.srt");
+    if let Some(cues) = &cues {
+        fs::write(&subtitles_file, srt::render_from(cues, 1))?;
+        args.extend_from_slice(&["-i", subtitles_file.to_str().unwrap()]);
+    }
+    
+    args.extend_from_slice(&["-map", "0:a", "-map_metadata", "1", "-map_chapters", "1", "-c", "copy"]);
+    if let (Embedding::Subtitles(codec), Some(_)) = (&embedding, &cues) {
+        args.extend_from_slice(&["-map", "2", "-c:s", codec]);
+    }
+    if extension == "mp3" {
+        args.extend_from_slice(&["-id3v2_version", "3"]);
+    }
+    
+    // Same extension, so ffmpeg picks the same container
+    let embedded = audio.with_extension(format!("embedding.{}", extension));
+    args.push(embedded.to_str().unwrap());
+    
+    debug!("Embedding the transcript in {:?}", audio);
+    if let Err(e) = config.command_runner.run("ffmpeg", &args) {
+        let _ = fs::remove_file(&embedded);
+        return Err(e);
+    }
+    fs::rename(&embedded, audio)?;
+    
+    let how = if cues.is_some() { "a subtitle track" } else { "lyrics" };
+    info!("Embedded the transcript in {:?} as {}", audio, how);
+    Ok(())
+}
+
+/// A transcript's timed cues, or `None` for formats without timestamps
+fn timed_cues(content: &str, format: OutputFormat) -> Result<Option<Vec<Cue>>> {
+    Ok(match format {
+        OutputFormat::Srt => Some(srt::parse(content)?),
+        OutputFormat::Vtt => Some(vtt::parse(content)?),
+        OutputFormat::Json | OutputFormat::All => Some(JsonTranscript::parse(content)?.cues()),
+        OutputFormat::Txt | OutputFormat::Html | OutputFormat::Md => None,
+    })
+}
+
+/// Escape a value for an ffmetadata file, where `=`, `;`, `#`, `\`, and newlines are special
+fn escape_metadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
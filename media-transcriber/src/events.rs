@@ -0,0 +1,106 @@
+use anyhow::Result;
+use log::debug;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A machine-readable progress or result event
+/// 
+/// Events are written as one JSON object per line, tagged by an `event` field.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event<'a> {
+    /// Processing of a source started
+    Start { source: &'a str },
+    /// Bytes received for a download; `total` is unknown without a Content-Length
+    DownloadProgress {
+        url: &'a str,
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    /// A chunk of an audio file is being transcribed
+    TranscribeProgress {
+        file: &'a Path,
+        chunk: usize,
+        chunks: usize,
+    },
+    /// An item was transcribed successfully
+    ItemComplete {
+        title: &'a str,
+        source_url: &'a str,
+        transcript_path: &'a Path,
+    },
+    /// An item failed to download or transcribe
+    ItemFailed {
+        title: &'a str,
+        source_url: &'a str,
+        error: String,
+    },
+    /// The whole run finished
+    Done { success: bool },
+}
+
+/// Destination for JSON line events
+pub struct EventSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl EventSink {
+    /// Write events to stdout
+    pub fn stdout() -> Self {
+        Self {
+            writer: Mutex::new(Box::new(io::stdout())),
+        }
+    }
+    
+    /// Write events to an already-open file descriptor (e.g. a pipe set up by a wrapper)
+    /// 
+    /// The descriptor is duplicated, so the caller's stays open. Standard input,
+    /// output, and error are refused; `--json-events` covers stdout.
+    #[cfg(unix)]
+    pub fn from_fd(fd: i32) -> Result<Self> {
+        use std::os::unix::io::FromRawFd;
+        
+        if fd <= 2 {
+            return Err(anyhow::anyhow!(
+                "Invalid file descriptor for events: {} (0-2 are stdin, stdout, and stderr; use --json-events for stdout)",
+                fd
+            ));
+        }
+        
+        // SAFETY: dup only reads the descriptor table; a closed descriptor just makes it fail
+        let duplicate = unsafe { libc::dup(fd) };
+        if duplicate < 0 {
+            return Err(anyhow::anyhow!("File descriptor {} isn't open: {}", fd, io::Error::last_os_error()));
+        }
+        
+        // SAFETY: the duplicate was just created, so nothing else owns it
+        let file = unsafe { std::fs::File::from_raw_fd(duplicate) };
+        Ok(Self {
+            writer: Mutex::new(Box::new(file)),
+        })
+    }
+    
+    /// Writing to arbitrary file descriptors is only supported on Unix
+    #[cfg(not(unix))]
+    pub fn from_fd(_fd: i32) -> Result<Self> {
+        Err(anyhow::anyhow!("--events-fd is only supported on Unix; use --json-events instead"))
+    }
+    
+    /// Emit an event; write errors are logged rather than aborting the run
+    pub fn emit(&self, event: &Event) {
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                debug!("Failed to serialize event: {}", e);
+                return;
+            }
+        };
+        
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+            debug!("Failed to write event: {}", e);
+        }
+    }
+}
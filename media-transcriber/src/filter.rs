@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::config::Timestamp;
+
+/// Metadata an episode or video is filtered on
+#[derive(Debug, Clone, Copy)]
+pub struct ItemMeta<'a> {
+    pub title: &'a str,
+    /// Duration in seconds, when known
+    pub duration: Option<f64>,
+    /// Publication/upload date, when known
+    pub date: Option<NaiveDate>,
+}
+
+/// Include/exclude rules loaded from a `--filter-file`
+///
+/// One rule per line, `#` starts a comment:
+///
+/// ```text
+/// include title ^Ep \d+
+/// exclude title (?i)trailer|bonus
+/// exclude duration < 600
+/// include date >= 2023-01-01
+/// ```
+///
+/// Durations are in seconds (or `MM:SS` / `HH:MM:SS`), dates are `YYYY-MM-DD`.
+/// An item is kept when it matches every `include` rule and no `exclude` rule.
+/// Duration and date rules never match items where that value is unknown.
+#[derive(Debug, Default)]
+pub struct FilterRules {
+    rules: Vec<Rule>,
+}
+
+/// A single include or exclude rule
+#[derive(Debug)]
+struct Rule {
+    include: bool,
+    condition: Condition,
+}
+
+/// What a rule tests
+#[derive(Debug)]
+enum Condition {
+    Title(Regex),
+    Duration(Comparison, f64),
+    Date(Comparison, NaiveDate),
+}
+
+/// Comparison operator for duration and date rules
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Result<Self> {
+        match op {
+            "<" => Ok(Comparison::Less),
+            "<=" => Ok(Comparison::LessOrEqual),
+            "=" | "==" => Ok(Comparison::Equal),
+            ">=" => Ok(Comparison::GreaterOrEqual),
+            ">" => Ok(Comparison::Greater),
+            _ => Err(anyhow::anyhow!("Unknown comparison operator '{}'", op)),
+        }
+    }
+    
+    fn test<T: PartialOrd>(&self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+impl FilterRules {
+    /// Load rules from a file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read filter file {:?}", path))?;
+        Self::parse(&content).with_context(|| format!("Invalid filter file {:?}", path))
+    }
+    
+    /// Parse rules from the filter file format
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+        
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            
+            let rule = parse_rule(line).with_context(|| format!("Line {}: '{}'", i + 1, line))?;
+            rules.push(rule);
+        }
+        
+        Ok(Self { rules })
+    }
+    
+    /// Whether an item passes all rules
+    pub fn matches(&self, item: &ItemMeta) -> bool {
+        self.rules.iter().all(|rule| rule.condition.test(item) == rule.include)
+    }
+}
+
+impl Condition {
+    fn test(&self, item: &ItemMeta) -> bool {
+        match self {
+            Condition::Title(pattern) => pattern.is_match(item.title),
+            Condition::Duration(op, secs) => item.duration.is_some_and(|d| op.test(d, *secs)),
+            Condition::Date(op, date) => item.date.is_some_and(|d| op.test(d, *date)),
+        }
+    }
+}
+
+/// Parse `include|exclude FIELD ...`
+fn parse_rule(line: &str) -> Result<Rule> {
+    let (action, rest) = line.split_once(char::is_whitespace).context("Expected a field after the action")?;
+    let include = match action {
+        "include" => true,
+        "exclude" => false,
+        _ => return Err(anyhow::anyhow!("Rules must start with 'include' or 'exclude'")),
+    };
+    
+    let rest = rest.trim_start();
+    let (field, argument) = rest.split_once(char::is_whitespace).context("Expected a value after the field")?;
+    let argument = argument.trim();
+    
+    let condition = match field {
+        "title" => Condition::Title(Regex::new(argument)?),
+        "duration" => {
+            let (op, value) = split_comparison(argument)?;
+            Condition::Duration(op, parse_duration(value)?)
+        }
+        "date" => {
+            let (op, value) = split_comparison(argument)?;
+            Condition::Date(op, NaiveDate::parse_from_str(value, "%Y-%m-%d")?)
+        }
+        _ => return Err(anyhow::anyhow!("Unknown field '{}' (expected title, duration, or date)", field)),
+    };
+    
+    Ok(Rule { include, condition })
+}
+
+/// Split `OP VALUE` into the operator and the value
+fn split_comparison(argument: &str) -> Result<(Comparison, &str)> {
+    let (op, value) = argument.split_once(char::is_whitespace).context("Expected 'OP VALUE', e.g. '< 600'")?;
+    Ok((Comparison::parse(op)?, value.trim()))
+}
+
+/// Parse seconds, `MM:SS`, or `HH:MM:SS`, as `--start`/`--end` take them
+fn parse_duration(value: &str) -> Result<f64> {
+    Ok(Timestamp::from_str(value).map_err(anyhow::Error::msg)?.secs)
+}
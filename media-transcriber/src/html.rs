@@ -0,0 +1,116 @@
+use regex::Regex;
+
+use crate::srt::Cue;
+use crate::utils;
+
+/// Styles for the page, kept inline so the file works on its own
+const STYLE: &str = "body { font-family: sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.6; }
+.source { color: #555; word-break: break-all; }
+audio { width: 100%; }
+.timestamp { color: #888; font-size: 0.85em; font-variant-numeric: tabular-nums; margin-right: 0.5em; text-decoration: none; }
+.speaker { font-weight: bold; margin-right: 0.3em; }";
+
+/// Seeks the page's `<audio>` element, if it has one, when a timestamp is clicked
+const SCRIPT: &str = "document.addEventListener('click', function (event) {
+  var link = event.target.closest('a.timestamp');
+  var audio = document.querySelector('audio');
+  if (!link || !audio) return;
+  event.preventDefault();
+  audio.currentTime = parseFloat(link.closest('.segment').dataset.start);
+  audio.play();
+});";
+
+/// Render timed cues as a self-contained HTML page
+/// 
+/// Each cue becomes a `<span class="segment">` with its start in seconds as
+/// `data-start` and a clickable timestamp. Sources that point straight at an
+/// audio file get an `<audio>` player the timestamps seek.
+pub fn render(title: &str, source: &str, cues: &[Cue]) -> String {
+    let voice = Regex::new(r"^<v(?:\.[^\s>]+)* ([^>]*)>").unwrap();
+    let mut output = String::new();
+    
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>{}</title>\n", escape(title)));
+    output.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    output.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    if source.starts_with("http://") || source.starts_with("https://") {
+        output.push_str(&format!("<p class=\"source\"><a href=\"{0}\">{0}</a></p>\n", escape(source)));
+    } else {
+        output.push_str(&format!("<p class=\"source\">{}</p>\n", escape(source)));
+    }
+    if utils::has_audio_extension(source) {
+        output.push_str(&format!("<audio controls preload=\"none\" src=\"{}\"></audio>\n", escape(source)));
+    }
+    
+    output.push_str("<main>\n");
+    for cue in cues {
+        let secs = cue.start_ms as f64 / 1000.0;
+        let text = unescape_vtt(&cue.text.replace('\n', " "));
+        let (speaker, text) = match voice.captures(&text) {
+            Some(captures) => (Some(captures[1].trim().to_string()), text[captures[0].len()..].trim().to_string()),
+            None => (None, text.trim().to_string()),
+        };
+        
+        output.push_str(&format!(
+            "<p><span class=\"segment\" data-start=\"{0:.3}\"><a class=\"timestamp\" href=\"#t={0:.3}\">{1}</a>",
+            secs,
+            timestamp(cue.start_ms)
+        ));
+        if let Some(speaker) = speaker {
+            output.push_str(&format!("<span class=\"speaker\">{}:</span>", escape(&speaker)));
+        }
+        output.push_str(&format!("{}</span></p>\n", escape(&text)));
+    }
+    output.push_str("</main>\n");
+    
+    output.push_str(&format!("<script>\n{}\n</script>\n</body>\n</html>\n", SCRIPT));
+    output
+}
+
+/// The segments' text from a page made by [`render`], one segment per line
+pub fn plain_text(content: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let timestamp = Regex::new(r#"<a class="timestamp"[^>]*>[^<]*</a>"#).unwrap();
+    
+    content
+        .lines()
+        .filter(|line| line.contains("class=\"segment\""))
+        .map(|line| unescape(tags.replace_all(&timestamp.replace_all(line, ""), "").trim()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape text for use in HTML content and attribute values
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Undo [`escape`]
+fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Undo WebVTT's escaping of cue text, leaving voice tags in place
+pub fn unescape_vtt(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", "\u{a0}")
+        .replace("&amp;", "&")
+}
+
+/// Timestamp shown next to a segment, e.g. `03:25` or `1:02:03`
+pub fn timestamp(ms: u64) -> String {
+    let total = ms / 1000;
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::srt::Cue;
+
+/// Transcript written by `--format json`, with times in seconds
+/// 
+/// `words` is only filled in with `--word-timestamps`. Finished transcripts
+/// in every format are read into one of these for the output writers.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct JsonTranscript {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+}
+
+/// A timed stretch of the transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A single timed word
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+impl JsonTranscript {
+    /// Parse a transcript written by [`Self::render`] (or one line written by [`Self::render_line`])
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(serde_json::from_str(content)?)
+    }
+    
+    /// Render the transcript as pretty-printed JSON
+    pub fn render(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+    
+    /// Render the transcript on a single line, for appending chunk by chunk
+    pub fn render_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+    
+    /// Move every segment and word `secs` later, e.g. to a chunk's position in the whole recording
+    pub fn shift(&mut self, secs: f64) {
+        for segment in &mut self.segments {
+            segment.start += secs;
+            segment.end += secs;
+        }
+        for word in &mut self.words {
+            word.start += secs;
+            word.end += secs;
+        }
+    }
+    
+    /// Drop segments and words centred before `secs`, which the previous chunk's overlap already covered
+    /// 
+    /// The text is rebuilt from the remaining segments.
+    pub fn drop_before(&mut self, secs: f64) {
+        if secs <= 0.0 {
+            return;
+        }
+        self.segments.retain(|segment| (segment.start + segment.end) / 2.0 >= secs);
+        self.words.retain(|word| (word.start + word.end) / 2.0 >= secs);
+        self.text = self.segments_text();
+    }
+    
+    /// Append a later part of the same recording
+    pub fn append(&mut self, other: JsonTranscript) {
+        let text = other.text.trim();
+        if !text.is_empty() {
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            self.text.push_str(text);
+        }
+        self.language = self.language.take().or(other.language);
+        self.segments.extend(other.segments);
+        self.words.extend(other.words);
+    }
+    
+    /// End of the last segment or word, in seconds
+    pub fn end(&self) -> Option<f64> {
+        let segments = self.segments.iter().map(|segment| segment.end);
+        let words = self.words.iter().map(|word| word.end);
+        segments.chain(words).reduce(f64::max)
+    }
+    
+    /// The segments as caption cues
+    pub fn cues(&self) -> Vec<Cue> {
+        self.segments
+            .iter()
+            .map(|segment| Cue {
+                start_ms: (segment.start * 1000.0).round() as u64,
+                end_ms: (segment.end * 1000.0).round() as u64,
+                text: segment.text.trim().to_string(),
+            })
+            .collect()
+    }
+    
+    /// Transcript made of caption cues, one segment each
+    pub fn from_cues(cues: &[Cue]) -> Self {
+        let mut transcript = Self {
+            segments: cues
+                .iter()
+                .map(|cue| Segment {
+                    start: cue.start_ms as f64 / 1000.0,
+                    end: cue.end_ms as f64 / 1000.0,
+                    text: cue.text.clone(),
+                })
+                .collect(),
+            ..Default::default()
+        };
+        transcript.text = transcript.segments_text();
+        transcript
+    }
+    
+    /// Segment texts joined into one paragraph
+    pub fn segments_text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
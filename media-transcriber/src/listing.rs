@@ -0,0 +1,156 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+use crate::audio_url::AudioUrlProcessor;
+use crate::config::Config;
+use crate::local_file::LocalFileProcessor;
+use crate::podcast::PodcastProcessor;
+use crate::utils;
+use crate::youtube::YouTubeProcessor;
+
+/// An episode, video, or file that would be processed, as shown by `list`
+#[derive(Debug, Serialize)]
+pub struct ListedItem {
+    /// Podcast, channel, file, or audio URL the item belongs to
+    pub source: String,
+    pub title: String,
+    /// Publication/upload date, when known
+    pub date: Option<NaiveDate>,
+    /// Duration in seconds, when known
+    pub duration: Option<f64>,
+    /// Audio URL, video URL, or file path
+    pub url: String,
+}
+
+/// List the items of a source, applying the same selection as processing it would
+pub async fn list_source(source_url: &str, config: &Config) -> Result<Vec<ListedItem>> {
+    if LocalFileProcessor::is_local_file_path(source_url) {
+        let path = LocalFileProcessor::resolve_path(source_url);
+        let title = path
+            .file_stem()
+            .map_or_else(|| source_url.to_string(), |stem| stem.to_string_lossy().into_owned());
+        Ok(vec![ListedItem {
+            source: source_url.to_string(),
+            title,
+            date: None,
+            duration: utils::audio_duration(&*config.command_runner, &path).ok(),
+            url: source_url.to_string(),
+        }])
+    } else if source_url.contains("youtube.com") || source_url.contains("youtu.be") {
+        YouTubeProcessor::new(config).list(source_url).await
+    } else if AudioUrlProcessor::is_audio_url(source_url, config).await {
+        Ok(vec![AudioUrlProcessor::new(config).list(source_url)])
+    } else {
+        PodcastProcessor::new(config).list(source_url).await
+    }
+}
+
+/// Print items as a table, grouped under their source and numbered within it
+pub fn print_table(items: &[ListedItem]) {
+    let mut current_source = None;
+    let mut number = 0;
+    for item in items {
+        if current_source != Some(&item.source) {
+            if current_source.is_some() {
+                println!();
+            }
+            println!("{}", item.source);
+            println!("{}", table_header());
+            current_source = Some(&item.source);
+            number = 0;
+        }
+        
+        number += 1;
+        println!("{}", table_row(number, item));
+    }
+}
+
+/// Column headings of the item table
+fn table_header() -> String {
+    format!("{:>4}  {:<10}  {:>10}  Title", "#", "Date", "Duration")
+}
+
+/// An item's line in the table
+fn table_row(number: usize, item: &ListedItem) -> String {
+    format!(
+        "{:>4}  {:<10}  {:>10}  {}",
+        number,
+        item.date.map_or("-".to_string(), |date| date.to_string()),
+        utils::format_duration(item.duration),
+        item.title
+    )
+}
+
+/// Let the user hand-pick items for `--interactive`
+/// 
+/// Lists the items numbered (as described by `listed`) and asks for a
+/// selection like `1-3,7,10`, or `all`; an empty answer picks nothing. The
+/// prompt goes to stderr so stdout stays clean for `--json-events`. Returns
+/// the picked items and the rest, both in their original order.
+pub fn pick_interactively<T>(items: Vec<T>, listed: impl Fn(&T) -> ListedItem) -> Result<(Vec<T>, Vec<T>)> {
+    if items.is_empty() {
+        return Ok((items, Vec::new()));
+    }
+    
+    let listed_items: Vec<ListedItem> = items.iter().map(listed).collect();
+    eprintln!();
+    eprintln!("{}", listed_items[0].source);
+    eprintln!("{}", table_header());
+    for (i, item) in listed_items.iter().enumerate() {
+        eprintln!("{}", table_row(i + 1, item));
+    }
+    
+    let selected = loop {
+        eprint!("Items to transcribe (e.g. 1-3,7,10, or 'all'; empty for none): ");
+        io::stderr().flush()?;
+        
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer)? == 0 {
+            warn!("No selection given (stdin is closed); skipping all {} items", items.len());
+            break BTreeSet::new();
+        }
+        match parse_selection(&answer, items.len()) {
+            Ok(selected) => break selected,
+            Err(e) => eprintln!("{}", e),
+        }
+    };
+    info!("Selected {} of {} items", selected.len(), items.len());
+    
+    let (picked, rest): (Vec<_>, Vec<_>) = items.into_iter().enumerate().partition(|(i, _)| selected.contains(i));
+    Ok((
+        picked.into_iter().map(|(_, item)| item).collect(),
+        rest.into_iter().map(|(_, item)| item).collect(),
+    ))
+}
+
+/// Parse a selection like `1-3,7,10` (or `all`) of items numbered 1 to `count` into 0-based indices
+fn parse_selection(input: &str, count: usize) -> Result<BTreeSet<usize>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+    
+    let number = |s: &str| {
+        s.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=count).contains(n))
+            .ok_or_else(|| format!("'{}' isn't a number from 1 to {}", s.trim(), count))
+    };
+    let mut selected = BTreeSet::new();
+    for part in input.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (number(first)?, number(last)?),
+            None => (number(part)?, number(part)?),
+        };
+        if first > last {
+            return Err(format!("'{}' runs backwards", part));
+        }
+        selected.extend(first - 1..last);
+    }
+    Ok(selected)
+}
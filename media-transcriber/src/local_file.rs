@@ -1,12 +1,25 @@
 use anyhow::Result;
-use log::{debug, info};
+use log::info;
 use std::path::{Path, PathBuf};
 use std::fs;
+use tempfile::tempdir;
+use url::Url;
 
 use crate::config::Config;
+use crate::manifest::{Manifest, SourceKind};
+use crate::template::{ItemNaming, ItemPaths, AUDIO_FILE_NAME, ERROR_FILE_NAME};
 use crate::transcription::TranscriptionService;
 use crate::utils;
 
+/// Directory that local files' transcripts go under
+const LOCAL_FILES_DIR: &str = "local_files";
+
+/// What `{channel}` resolves to for local files in an output template
+const LOCAL_FILES_CHANNEL: &str = "Local Files";
+
+/// Video containers whose audio track is extracted and transcribed
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "m4v", "mkv", "mov", "webm", "avi"];
+
 /// Processor for local media files
 pub struct LocalFileProcessor<'a> {
     /// Configuration for the processor
@@ -24,11 +37,11 @@ impl<'a> LocalFileProcessor<'a> {
     /// This function:
     /// 1. Validates the file exists and is a supported format
     /// 2. Creates an output directory for the transcription
-    /// 3. Transcribes the file using the Whisper API
+    /// 3. Transcribes the file (for videos, their first audio track) using the Whisper API
     /// 4. Saves the transcript to the output directory
     pub async fn process(&self, file_path: &str) -> Result<()> {
         // Convert string path to PathBuf
-        let file_path = PathBuf::from(file_path);
+        let file_path = Self::resolve_path(file_path);
         
         // Validate file exists
         if !file_path.exists() {
@@ -40,9 +53,21 @@ impl<'a> LocalFileProcessor<'a> {
             .and_then(|ext| ext.to_str())
             .unwrap_or("");
             
-        // Check if file is an MP3
-        if extension.to_lowercase() != "mp3" {
-            return Err(anyhow::anyhow!("Unsupported file format: {}", extension));
+        // Check if file is an MP3 or a video with an audio track to extract
+        let extension = extension.to_lowercase();
+        let is_video = VIDEO_EXTENSIONS.contains(&extension.as_str());
+        if extension != "mp3" && !is_video {
+            return Err(anyhow::anyhow!(
+                "Unsupported file format: {} (expected mp3 or a video: {})",
+                extension,
+                VIDEO_EXTENSIONS.join(", ")
+            ));
+        }
+        
+        // Estimate cost and confirm before transcribing
+        let duration = utils::audio_duration(&*self.config.command_runner, &file_path).ok();
+        if !utils::confirm_estimated_cost(self.config, &[duration])? {
+            return Ok(());
         }
         
         // Get file name for output directory
@@ -53,33 +78,154 @@ impl<'a> LocalFileProcessor<'a> {
         // Sanitize filename for directory name
         let sanitized_name = utils::sanitize_filename(file_stem);
         
-        // Create output directory
-        let output_dir = self.config.output_dir.join("local_files").join(&sanitized_name);
+        // Create output directory (local files aren't grouped by date)
+        let modified = fs::metadata(&file_path).and_then(|metadata| metadata.modified()).ok();
+        let naming = ItemNaming {
+            channel: LOCAL_FILES_CHANNEL,
+            title: file_stem,
+            id: file_stem,
+            date: modified.map(|time| chrono::DateTime::<chrono::Local>::from(time).date_naive()),
+        };
+        let paths = self.config.claim_item_paths(
+            &sanitized_name,
+            &naming,
+            None,
+            |dir_name, naming| match &self.config.output_template {
+                _ if self.config.inplace => self.inplace_paths(&file_path, naming),
+                Some(template) => template.item_paths(&self.config.output_dir, naming, self.config.format, "file_info.txt"),
+                None => {
+                    let dir = utils::item_dir(&self.config.output_dir.join(LOCAL_FILES_DIR), dir_name);
+                    ItemPaths {
+                        transcript: dir.join(self.config.format.transcript_file_name()),
+                        info: dir.join("file_info.txt"),
+                        audio: dir.join(AUDIO_FILE_NAME),
+                        error: dir.join(ERROR_FILE_NAME),
+                        dir,
+                    }
+                }
+            },
+            |info| utils::info_field(info, "File").is_some_and(|existing| !is_same_file(Path::new(existing), &file_path)),
+        );
+        let Some(paths) = paths else {
+            return Ok(());
+        };
+        let output_dir = paths.dir.clone();
         fs::create_dir_all(&output_dir)?;
         
         // Save file info
         let file_info = format!(
-            "File: {}\nSize: {} bytes\nTranscribed: {}",
+            "File: {}\nSize: {} bytes\nTranscribed: {}\n",
             file_path.display(),
             fs::metadata(&file_path)?.len(),
             chrono::Local::now().to_rfc3339()
         );
-        fs::write(output_dir.join("file_info.txt"), file_info)?;
+        fs::write(&paths.info, file_info)?;
         
         // Create transcript output path
-        let transcript_path = output_dir.join("transcript.txt");
+        let transcript_path = paths.transcript.clone();
         
         // Create transcription service
         let transcription_service = TranscriptionService::new(self.config);
         
-        // Transcribe the file
+        let source_url = file_path.display().to_string();
+        let mut manifest = Manifest::new(self.config, SourceKind::LocalFile, file_stem, &source_url, self.config.language.clone());
+        
+        if self.config.dry_run {
+            info!(
+                "[dry run] Would transcribe: {} (duration: {})",
+                file_path.display(),
+                utils::format_duration(duration)
+            );
+            manifest.skipped(file_stem, &source_url, duration, "dry run");
+            return self.write_manifest(&manifest, &output_dir);
+        }
+        
+        if self.config.detect_language_only {
+            let result = transcription_service.report_language(file_stem, &source_url, &paths.info).await;
+            match &result {
+                Ok(()) => manifest.skipped(file_stem, &source_url, duration, "language detection only"),
+                Err(e) => manifest.failed(file_stem, &source_url, duration, e),
+            }
+            self.write_manifest(&manifest, &output_dir)?;
+            return result;
+        }
+        
+        // Transcribe the file, or the audio track extracted from a video
         info!("Transcribing local file: {:?}", file_path);
-        transcription_service.transcribe_file(&file_path, &transcript_path).await?;
+        let temp_dir = tempdir()?;
+        let result = utils::ItemDeadline::start(self.config)
+            .run(async {
+                let audio_file = if is_video {
+                    self.extract_video_audio(&file_path, temp_dir.path())?
+                } else {
+                    file_path.clone()
+                };
+                transcription_service.transcribe_file(&audio_file, &transcript_path, file_stem, &source_url).await
+            })
+            .await;
+        match &result {
+            Ok(()) => {
+                manifest.success(
+                    file_stem,
+                    &source_url,
+                    &transcription_service.transcript_path(&transcript_path),
+                    duration,
+                );
+                paths.clear_failure();
+            }
+            Err(e) => {
+                manifest.failed(file_stem, &source_url, duration, e);
+                paths.record_failure(e);
+            }
+        }
+        self.write_manifest(&manifest, &output_dir)?;
+        result?;
+        
+        transcription_service.record_detection(&paths.info, file_stem)?;
         
         info!("Transcription complete: {:?}", transcript_path);
         Ok(())
     }
     
+    /// Paths for `--inplace`, next to the source file and named after it, like a file output template
+    /// 
+    /// The audio path is the source itself, which is already kept.
+    fn inplace_paths(&self, file_path: &Path, naming: &ItemNaming) -> ItemPaths {
+        let dir = file_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let transcript = dir.join(format!("{}.{}", naming.title, self.config.format.extension()));
+        ItemPaths {
+            info: transcript.with_extension("info.txt"),
+            audio: file_path.to_path_buf(),
+            error: transcript.with_extension("error.txt"),
+            transcript,
+            dir,
+        }
+    }
+    
+    /// Write the item's manifest, or with `--inplace` only count its outcome
+    /// 
+    /// In place there's no directory of the file's own, so a `manifest.json`
+    /// would be overwritten by every other file in the same folder.
+    fn write_manifest(&self, manifest: &Manifest, dir: &Path) -> Result<()> {
+        if self.config.inplace {
+            manifest.count();
+            return Ok(());
+        }
+        manifest.write(dir)
+    }
+    
+    /// Extract a video's audio track to an MP3 in `temp_dir`, failing clearly if it has none
+    fn extract_video_audio(&self, video_file: &Path, temp_dir: &Path) -> Result<PathBuf> {
+        if !utils::has_audio_stream(&*self.config.command_runner, video_file)? {
+            return Err(anyhow::anyhow!("Video has no audio track to transcribe: {:?}", video_file));
+        }
+        
+        info!("Extracting audio track from video: {:?}", video_file);
+        let audio_file = temp_dir.join("audio.mp3");
+        utils::extract_audio(&*self.config.command_runner, video_file, &audio_file, self.config.transcode_threads)?;
+        Ok(audio_file)
+    }
+    
     /// Check if a path is a local file path rather than a URL
     pub fn is_local_file_path(path: &str) -> bool {
         // Check if path starts with http:// or https://
@@ -88,7 +234,78 @@ impl<'a> LocalFileProcessor<'a> {
         }
         
         // Check if path exists as a local file
-        let path_buf = PathBuf::from(path);
+        let path_buf = Self::resolve_path(path);
         path_buf.exists() && path_buf.is_file()
     }
+    
+    /// Turn a local source into a path, reading `file://` URLs and expanding a leading `~` to the home directory
+    pub fn resolve_path(path: &str) -> PathBuf {
+        if path.starts_with("file://") {
+            if let Some(file_path) = Url::parse(path).ok().and_then(|url| url.to_file_path().ok()) {
+                return file_path;
+            }
+        }
+        
+        let rest = match path.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with(std::path::is_separator) => rest,
+            _ => return PathBuf::from(path),
+        };
+        match utils::home_dir() {
+            Some(home) => home.join(rest.trim_start_matches(std::path::is_separator)),
+            None => PathBuf::from(path),
+        }
+    }
+}
+
+/// Whether two paths name the same file, however they were written
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn resolve_path_expands_the_home_directory() {
+        let home = utils::home_dir().expect("tests need HOME set");
+        
+        assert_eq!(LocalFileProcessor::resolve_path("~"), home);
+        assert_eq!(LocalFileProcessor::resolve_path("~/x.mp3"), home.join("x.mp3"));
+        assert_eq!(LocalFileProcessor::resolve_path("~user/x"), PathBuf::from("~user/x"));
+        assert_eq!(LocalFileProcessor::resolve_path("a/~/x"), PathBuf::from("a/~/x"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn resolve_path_decodes_file_urls() {
+        assert_eq!(LocalFileProcessor::resolve_path("file:///tmp/a%20b.mp3"), PathBuf::from("/tmp/a b.mp3"));
+    }
+    
+    #[test]
+    fn local_files_are_found_by_relative_and_absolute_paths_and_file_urls() {
+        // Created in the working directory so it can be reached by a relative path
+        let file = tempfile::Builder::new().suffix(".mp3").tempfile_in(".").unwrap();
+        let absolute = fs::canonicalize(file.path()).unwrap();
+        let relative = absolute.file_name().unwrap().to_str().unwrap();
+        let url = Url::from_file_path(&absolute).unwrap();
+        
+        assert!(LocalFileProcessor::is_local_file_path(relative));
+        assert!(LocalFileProcessor::is_local_file_path(&format!("./{}", relative)));
+        assert!(LocalFileProcessor::is_local_file_path(absolute.to_str().unwrap()));
+        assert!(LocalFileProcessor::is_local_file_path(url.as_str()));
+        
+        let dir = absolute.parent().unwrap();
+        assert!(!LocalFileProcessor::is_local_file_path(dir.to_str().unwrap()));
+        assert!(!LocalFileProcessor::is_local_file_path("no-such-file.mp3"));
+    }
+    
+    #[test]
+    fn urls_are_not_local_files() {
+        assert!(!LocalFileProcessor::is_local_file_path("http://example.com/episode.mp3"));
+        assert!(!LocalFileProcessor::is_local_file_path("https://example.com/episode.mp3"));
+    }
 }
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use log::debug;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::events::{Event, EventSink};
+use crate::summary::ItemTally;
+
+/// File name of the manifest written at each source root
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Machine-readable summary of a processed source
+/// 
+/// Item outcomes are also forwarded to the event sink, if one is configured.
+#[derive(Serialize)]
+pub struct Manifest<'a> {
+    /// The feed, channel, or file that was processed
+    pub source: SourceInfo,
+    /// Items found in the source and what happened to them
+    pub items: Vec<ManifestItem>,
+    #[serde(skip)]
+    events: Option<&'a EventSink>,
+    #[serde(skip)]
+    tally: &'a ItemTally,
+}
+
+/// Kind of source a manifest describes
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Podcast,
+    Youtube,
+    LocalFile,
+    AudioUrl,
+}
+
+/// Description of a feed, channel, or local file
+#[derive(Debug, Serialize)]
+pub struct SourceInfo {
+    pub kind: SourceKind,
+    pub title: String,
+    pub url: String,
+    pub language: Option<String>,
+}
+
+/// Outcome of processing a single item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    Success,
+    Skipped,
+    Failed,
+}
+
+/// A single episode, video, or file in a manifest
+#[derive(Debug, Serialize)]
+pub struct ManifestItem {
+    pub title: String,
+    pub source_url: String,
+    /// Transcript path, only set when transcription succeeded
+    pub transcript_path: Option<PathBuf>,
+    /// Duration in seconds, when known
+    pub duration: Option<f64>,
+    pub status: ItemStatus,
+    /// Error message for failed items, or the reason an item was skipped
+    pub message: Option<String>,
+}
+
+impl<'a> Manifest<'a> {
+    /// Create an empty manifest for a source
+    pub fn new(config: &'a Config, kind: SourceKind, title: &str, url: &str, language: Option<String>) -> Self {
+        Self {
+            source: SourceInfo {
+                kind,
+                title: title.to_string(),
+                url: url.to_string(),
+                language,
+            },
+            items: Vec::new(),
+            events: config.events.as_ref(),
+            tally: &config.item_tally,
+        }
+    }
+    
+    /// Record a successfully transcribed item
+    pub fn success(&mut self, title: &str, source_url: &str, transcript_path: &Path, duration: Option<f64>) {
+        if let Some(events) = self.events {
+            events.emit(&Event::ItemComplete {
+                title,
+                source_url,
+                transcript_path,
+            });
+        }
+        
+        self.items.push(ManifestItem {
+            title: title.to_string(),
+            source_url: source_url.to_string(),
+            transcript_path: Some(transcript_path.to_path_buf()),
+            duration,
+            status: ItemStatus::Success,
+            message: None,
+        });
+    }
+    
+    /// Record an item that was deliberately not transcribed
+    pub fn skipped(&mut self, title: &str, source_url: &str, duration: Option<f64>, reason: &str) {
+        self.items.push(ManifestItem {
+            title: title.to_string(),
+            source_url: source_url.to_string(),
+            transcript_path: None,
+            duration,
+            status: ItemStatus::Skipped,
+            message: Some(reason.to_string()),
+        });
+    }
+    
+    /// Record an item that failed to download or transcribe
+    pub fn failed(&mut self, title: &str, source_url: &str, duration: Option<f64>, error: &anyhow::Error) {
+        if let Some(events) = self.events {
+            events.emit(&Event::ItemFailed {
+                title,
+                source_url,
+                error: format!("{:#}", error),
+            });
+        }
+        
+        self.items.push(ManifestItem {
+            title: title.to_string(),
+            source_url: source_url.to_string(),
+            transcript_path: None,
+            duration,
+            status: ItemStatus::Failed,
+            message: Some(format!("{:#}", error)),
+        });
+    }
+    
+    /// Add the item outcomes to the run's totals without writing a manifest file
+    pub fn count(&self) {
+        self.tally.add(&self.items);
+    }
+    
+    /// Write the manifest as `manifest.json` in the given directory
+    /// 
+    /// This also adds the item outcomes to the run's totals.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        self.count();
+        
+        let manifest_file = dir.join(MANIFEST_FILE_NAME);
+        fs::write(&manifest_file, serde_json::to_string_pretty(self)?)?;
+        debug!("Saved manifest to: {:?}", manifest_file);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use regex::Regex;
+
+use crate::html;
+use crate::srt::Cue;
+use crate::utils::Chapter;
+
+/// Render timed cues as a Markdown document
+/// 
+/// The title is the top-level heading, followed by the source and one
+/// paragraph per cue, led by its bold start time. Chapters become `##`
+/// headings ahead of the first cue they contain, and voice spans become bold
+/// `Speaker:` labels.
+pub fn render(title: &str, source: &str, cues: &[Cue], chapters: &[Chapter]) -> String {
+    let voice = Regex::new(r"^<v(?:\.[^\s>]+)* ([^>]*)>").unwrap();
+    let tag = Regex::new(r"</?[^>]+>").unwrap();
+    let mut output = format!("# {}\n\n", title);
+    
+    if source.starts_with("http://") || source.starts_with("https://") {
+        output.push_str(&format!("<{}>\n\n", source));
+    } else {
+        output.push_str(&format!("{}\n\n", source));
+    }
+    
+    let mut chapters = chapters.iter().enumerate().peekable();
+    for cue in cues {
+        while let Some((i, chapter)) = chapters.next_if(|(_, chapter)| (chapter.start * 1000.0) as u64 <= cue.start_ms) {
+            output.push_str(&format!("## {}\n\n", chapter_title(i, chapter)));
+        }
+        
+        let text = cue.text.replace('\n', " ");
+        let (speaker, text) = match voice.captures(&text) {
+            Some(captures) => (Some(captures[1].trim().to_string()), &text[captures[0].len()..]),
+            None => (None, text.as_str()),
+        };
+        let text = html::unescape_vtt(tag.replace_all(text, "").trim());
+        let text = match speaker {
+            Some(speaker) => format!("**{}:** {}", html::unescape_vtt(&speaker), text),
+            None => text,
+        };
+        output.push_str(&format!("**[{}]** {}\n\n", html::timestamp(cue.start_ms), text));
+    }
+    
+    output.truncate(output.trim_end().len());
+    output.push('\n');
+    output
+}
+
+/// The cues' text from a document made by [`render`], one cue per line
+/// 
+/// Speaker labels become `Speaker: ` prefixes, as in other formats' plain text.
+pub fn plain_text(content: &str) -> String {
+    let timestamp = Regex::new(r"^\*\*\[[0-9:]+\]\*\* ").unwrap();
+    let speaker = Regex::new(r"^\*\*([^*]+):\*\* ").unwrap();
+    
+    content
+        .lines()
+        .filter(|line| timestamp.is_match(line))
+        .map(|line| {
+            let text = timestamp.replace(line, "");
+            speaker.replace(&text, "$1: ").into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A chapter's heading, numbered when it has no title
+fn chapter_title(index: usize, chapter: &Chapter) -> String {
+    chapter.title.clone().unwrap_or_else(|| format!("Chapter {}", index + 1))
+}
@@ -1,12 +1,22 @@
 use anyhow::Result;
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
 use log::{debug, error, info, warn};
+use regex::Regex;
 use rss::{Channel, Item};
-use std::fs;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tempfile::tempdir;
 
-use crate::config::Config;
+use crate::combined::CombinedTranscript;
+use crate::config::{Config, Order};
+use crate::embed;
+use crate::filter::ItemMeta;
+use crate::listing::{self, ListedItem};
+use crate::manifest::{Manifest, SourceKind};
+use crate::template::{ItemNaming, ItemPaths};
 use crate::transcription::TranscriptionService;
 use crate::utils;
 
@@ -15,13 +25,137 @@ pub struct PodcastProcessor<'a> {
     config: &'a Config,
 }
 
+/// Podcast feed metadata, from either RSS or Atom
+struct PodcastFeed {
+    title: String,
+    description: String,
+    language: Option<String>,
+    author: Option<String>,
+    /// Items/entries in the feed, including ones without audio
+    item_count: usize,
+}
+
+impl PodcastFeed {
+    fn from_rss(channel: &Channel) -> Self {
+        Self {
+            title: channel.title.clone(),
+            description: channel.description.clone(),
+            language: channel.language.clone(),
+            author: channel.itunes_ext.as_ref().and_then(|ext| ext.author.clone()),
+            item_count: channel.items.len(),
+        }
+    }
+    
+    fn from_atom(feed: &atom_syndication::Feed) -> Self {
+        Self {
+            title: feed.title().as_str().trim().to_string(),
+            description: feed.subtitle().map(|text| text.as_str().trim().to_string()).unwrap_or_default(),
+            language: feed.lang().map(str::to_string),
+            author: feed.authors().first().map(|person| person.name().to_string()),
+            item_count: feed.entries().len(),
+        }
+    }
+}
+
 /// Podcast episode metadata
 struct PodcastEpisode {
     title: String,
     audio_url: String,
     pub_date: Option<DateTime<FixedOffset>>,
+    /// Duration in seconds, from `<itunes:duration>`
+    duration: Option<u64>,
+    /// Feed-unique identifier, from `<guid>`
+    guid: Option<String>,
+    /// Season number, from `<itunes:season>`
+    season: Option<u32>,
+    /// Episode number, from `<itunes:episode>`
+    episode: Option<u32>,
+}
+
+impl PodcastEpisode {
+    /// Duration in seconds as recorded in manifests
+    fn duration_secs(&self) -> Option<f64> {
+        self.duration.map(|secs| secs as f64)
+    }
+    
+    /// The episode as shown by `list` and `--interactive`
+    fn listed(&self, feed_title: &str) -> ListedItem {
+        ListedItem {
+            source: feed_title.to_string(),
+            title: self.title.clone(),
+            date: self.pub_date.map(|date| date.date_naive()),
+            duration: self.duration_secs(),
+            url: self.audio_url.clone(),
+        }
+    }
+    
+    /// Identifier recorded once the episode is transcribed: its GUID, or its sanitized title without one
+    fn state_id(&self) -> String {
+        self.guid.clone().unwrap_or_else(|| utils::sanitize_filename(&self.title))
+    }
+    
+    /// Short hash of the GUID (or audio URL without one), to tell same-titled episodes apart
+    fn id_hash(&self) -> String {
+        let id = self.guid.as_deref().unwrap_or(&self.audio_url);
+        format!("{:016x}", utils::stable_hash(id))[..8].to_string()
+    }
+    
+    /// Directory name for this episode, prefixed like `S02E05_Title` when numbered
+    fn dir_name(&self) -> String {
+        let title = utils::sanitize_filename(&self.title);
+        match (self.season, self.episode) {
+            (Some(season), Some(episode)) => format!("S{:02}E{:02}_{}", season, episode, title),
+            (None, Some(episode)) => format!("E{:02}_{}", episode, title),
+            _ => title,
+        }
+    }
+    
+    /// Whether this episode is the one referred to by a GUID, title, or number
+    /// 
+    /// Numbers can be a plain episode number (`42`) or include the season (`S2E5`).
+    fn matches_reference(&self, reference: &str) -> bool {
+        let reference = reference.trim();
+        if self.guid.as_deref() == Some(reference) || self.title.trim().eq_ignore_ascii_case(reference) {
+            return true;
+        }
+        
+        match parse_episode_number(reference) {
+            Some((Some(season), episode)) => self.season == Some(season) && self.episode == Some(episode),
+            Some((None, episode)) => self.episode == Some(episode),
+            None => false,
+        }
+    }
 }
 
+/// Fallback `<pubDate>` formats that include a UTC offset
+const PUB_DATE_FORMATS_WITH_ZONE: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S %z",
+    "%a, %d %b %Y %H:%M %z",
+    "%d %b %Y %H:%M:%S %z",
+];
+
+/// Fallback `<pubDate>` formats without a time zone
+const PUB_DATE_FORMATS_WITHOUT_ZONE: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%a, %d %b %Y %H:%M:%S",
+];
+
+/// File at a podcast's root listing transcribed episodes (GUIDs or sanitized titles), one per line
+const TRANSCRIBED_EPISODES_FILE: &str = "transcribed_episodes.txt";
+
+/// Longest time to wait for a feed to download
+const FEED_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Feeds with at most this many episodes are checked for being preview-only
+const PREVIEW_FEED_MAX_EPISODES: usize = 3;
+
+/// Episodes shorter than this are considered trailer-length, in seconds
+const TRAILER_MAX_DURATION_SECS: u64 = 5 * 60;
+
+/// Enclosure MIME types that say nothing about the content, so the URL's extension decides
+const GENERIC_MIME_TYPES: &[&str] = &["application/octet-stream", "binary/octet-stream", "application/binary"];
+
 impl<'a> PodcastProcessor<'a> {
     /// Create a new podcast processor
     pub fn new(config: &'a Config) -> Self {
@@ -32,86 +166,374 @@ impl<'a> PodcastProcessor<'a> {
     pub async fn process(&self, feed_url: &str) -> Result<()> {
         info!("Processing podcast feed: {}", feed_url);
         
-        // Download and parse RSS feed
-        let channel = self.download_feed(feed_url).await?;
+        // Download and parse RSS or Atom feed
+        let (feed, mut episodes) = self.download_feed(feed_url).await?;
+        if episodes.is_empty() {
+            let detail = match feed.item_count {
+                0 => "it has no items".to_string(),
+                count => format!("none of its {} items has an audio enclosure", count),
+            };
+            return Err(utils::NothingToTranscribe(format!(
+                "feed '{}' parsed but contained no transcribable audio episodes ({})",
+                feed.title, detail
+            ))
+            .into());
+        }
         
         // Create podcast directory
-        let podcast_dir = self.create_podcast_directory(&channel.title)?;
+        let podcast_dir = self.create_podcast_directory(&feed.title)?;
         
         // Save podcast info
-        self.save_podcast_info(&channel, feed_url, &podcast_dir)?;
+        self.save_podcast_info(&feed, feed_url, &podcast_dir)?;
         
-        // Extract episodes
-        let mut episodes = self.extract_episodes(&channel)?;
+        // Paid shows often publish a public feed containing only a trailer
+        if is_preview_feed(&episodes) {
+            warn!(
+                "Feed '{}' only contains {} trailer-like episode(s); it may be a preview of a premium show rather than the full feed",
+                feed.title,
+                episodes.len()
+            );
+        }
         
-        // Sort episodes by publication date (newest first)
-        episodes.sort_by(|a, b| {
-            b.pub_date.unwrap_or_default().cmp(&a.pub_date.unwrap_or_default())
-        });
+        let mut manifest = Manifest::new(
+            self.config,
+            SourceKind::Podcast,
+            &feed.title,
+            feed_url,
+            feed.language.clone(),
+        );
         
-        // Apply limit if specified
-        if let Some(limit) = self.config.limit {
-            if episodes.len() > limit {
-                info!("Limiting to {} episodes (out of {})", limit, episodes.len());
-                episodes.truncate(limit);
+        for (episode, reason) in self.select_episodes(feed_url, &feed.title, &podcast_dir, &mut episodes)? {
+            manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), reason);
+        }
+        
+        // Let the user hand-pick from what's left
+        if self.config.interactive {
+            let (picked, rest) = listing::pick_interactively(episodes, |episode| episode.listed(&feed.title))?;
+            for episode in rest {
+                manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), "not selected");
             }
+            episodes = picked;
         }
         
-        // Process each episode
-        let transcription_service = TranscriptionService::new(self.config);
+        // Estimate cost and confirm before downloading anything
+        if self.config.engine.usd_per_minute().is_some() {
+            let durations: Vec<Option<f64>> = episodes
+                .iter()
+                .map(|episode| {
+                    episode
+                        .duration_secs()
+                        .or_else(|| utils::probe_duration(&*self.config.command_runner, &episode.audio_url).ok())
+                })
+                .collect();
+            if !utils::confirm_estimated_cost(self.config, &durations)? {
+                manifest.write(&podcast_dir)?;
+                return Ok(());
+            }
+        }
+        
+        // Process each episode, in the feed's language unless one (or several, or auto) is configured
+        let feed_language = feed
+            .language
+            .as_deref()
+            .and_then(normalize_language_code)
+            .filter(|_| self.config.expected_languages.is_empty() && !self.config.auto_language);
+        if let Some(language) = feed_language.as_deref().filter(|_| self.config.language.is_none()) {
+            info!("Transcribing in '{}', the language declared by the feed (--language overrides this)", language);
+        }
+        let transcription_service = TranscriptionService::new(self.config).with_default_language(feed_language);
+        let mut combined = self
+            .config
+            .combined
+            .then(|| CombinedTranscript::new(&feed.title, self.config.format));
         
         for (i, episode) in episodes.iter().enumerate() {
             info!("Processing episode {}/{}: {}", i + 1, episodes.len(), episode.title);
             
             // Create episode directory
-            let episode_dir = podcast_dir.join(utils::sanitize_filename(&episode.title));
-            fs::create_dir_all(&episode_dir)?;
+            let Some(paths) = self.episode_paths(&feed.title, &podcast_dir, episode) else {
+                manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), "name taken by another episode");
+                continue;
+            };
+            fs::create_dir_all(&paths.dir)?;
+            
+            // Save episode info
+            self.save_episode_info(episode, &paths.info)?;
+            
+            if self.config.dry_run {
+                info!(
+                    "[dry run] Would transcribe: {} (published: {}, duration: {})",
+                    episode.title,
+                    episode.pub_date.map_or("unknown".to_string(), |date| date.to_rfc2822()),
+                    utils::format_duration(episode.duration_secs())
+                );
+                manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), "dry run");
+                continue;
+            }
+            
+            if self.config.detect_language_only {
+                match transcription_service.report_language(&episode.title, &episode.audio_url, &paths.info).await {
+                    Ok(()) => manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), "language detection only"),
+                    Err(e) => {
+                        error!("Failed to detect episode language: {}", e);
+                        manifest.failed(&episode.title, &episode.audio_url, episode.duration_secs(), &e);
+                    }
+                }
+                continue;
+            }
             
-            // Download audio file
+            // Download audio file, unless it's already cached
+            let deadline = utils::ItemDeadline::start(self.config);
             let temp_dir = tempdir()?;
-            let audio_file = temp_dir.path().join("episode.mp3");
+            let (audio_file, download) = match utils::cached_audio(self.config, &episode.audio_url) {
+                Some(cached) => (cached, Ok(())),
+                None => {
+                    let audio_file = temp_dir.path().join("episode.mp3");
+                    let download = deadline
+                        .run(utils::download_file(&episode.audio_url, &audio_file, self.config))
+                        .await;
+                    if download.is_ok() {
+                        utils::cache_audio(self.config, &episode.audio_url, &audio_file);
+                    }
+                    (audio_file, download)
+                }
+            };
             
-            match utils::download_file(&episode.audio_url, &audio_file).await {
+            match download {
                 Ok(_) => {
+                    let kept_audio = paths.audio.with_extension(utils::audio_extension(&episode.audio_url));
+                    utils::keep_audio(self.config, &audio_file, &kept_audio);
+                    
                     // Transcribe audio file
-                    let transcript_file = episode_dir.join("transcript.txt");
+                    let transcript_file = paths.transcript.clone();
+                    let transcription = transcription_service.transcribe_file(&audio_file, &transcript_file, &episode.title, &episode.audio_url);
                     
-                    if let Err(e) = transcription_service.transcribe_file(&audio_file, &transcript_file).await {
+                    if let Err(e) = deadline.run(transcription).await {
                         error!("Failed to transcribe episode: {}", e);
+                        manifest.failed(&episode.title, &episode.audio_url, episode.duration_secs(), &e);
+                        paths.record_failure(&e);
                         continue;
                     }
                     
                     info!("Successfully transcribed episode: {}", episode.title);
+                    transcription_service.record_detection(&paths.info, &episode.title)?;
+                    let transcript_file = transcription_service.transcript_path(&transcript_file);
+                    embed::embed_transcript(self.config, &kept_audio, &transcript_file, &[]);
+                    manifest.success(
+                        &episode.title,
+                        &episode.audio_url,
+                        &transcript_file,
+                        episode.duration_secs(),
+                    );
+                    record_transcribed_episode(&podcast_dir, episode);
+                    paths.clear_failure();
+                    if let Some(combined) = combined.as_mut() {
+                        combined.add(&episode.title, episode.pub_date.map(|date| date.date_naive()), &transcript_file);
+                    }
+                }
+                Err(e) if e.is::<utils::TooLarge>() => {
+                    warn!("Skipping episode {}: {}", episode.title, e);
+                    manifest.skipped(&episode.title, &episode.audio_url, episode.duration_secs(), "larger than --max-filesize");
+                    continue;
                 }
                 Err(e) => {
                     error!("Failed to download episode audio: {}", e);
+                    manifest.failed(&episode.title, &episode.audio_url, episode.duration_secs(), &e);
+                    paths.record_failure(&e);
                     continue;
                 }
             }
         }
         
+        manifest.write(&podcast_dir)?;
+        
+        if let Some(combined) = combined {
+            combined.write(&podcast_dir)?;
+        }
+        
         Ok(())
     }
     
-    /// Download and parse RSS feed
-    async fn download_feed(&self, feed_url: &str) -> Result<Channel> {
-        debug!("Downloading RSS feed: {}", feed_url);
+    /// List the episodes of a feed that would be processed, without downloading any audio
+    pub async fn list(&self, feed_url: &str) -> Result<Vec<ListedItem>> {
+        let (feed, mut episodes) = self.download_feed(feed_url).await?;
+        let podcast_dir = self.config.source_dir(&feed.title);
+        self.select_episodes(feed_url, &feed.title, &podcast_dir, &mut episodes)?;
+        
+        Ok(episodes.iter().map(|episode| episode.listed(&feed.title)).collect())
+    }
+    
+    /// Narrow a feed's episodes down to the ones to process, in processing order
+    /// 
+    /// Sorts newest first, then applies `--since-episode`, `--update`, the filters,
+    /// `--order`, shuffling, `--limit`, and `--max-minutes`. Returns the episodes
+    /// that were left out and why.
+    fn select_episodes(
+        &self,
+        feed_url: &str,
+        feed_title: &str,
+        podcast_dir: &Path,
+        episodes: &mut Vec<PodcastEpisode>,
+    ) -> Result<Vec<(PodcastEpisode, &'static str)>> {
+        let mut skipped = Vec::new();
+        
+        // Sort episodes by publication date (newest first), then by season/episode number
+        episodes.sort_by(|a, b| {
+            b.pub_date
+                .unwrap_or_default()
+                .cmp(&a.pub_date.unwrap_or_default())
+                .then_with(|| (b.season, b.episode).cmp(&(a.season, a.episode)))
+        });
+        
+        // Only keep episodes published after the one given with --since-episode
+        if let Some(reference) = &self.config.since_episode {
+            let position = find_episode(episodes, reference)?;
+            info!(
+                "Resuming after '{}': {} newer episodes",
+                episodes[position].title,
+                position
+            );
+            skipped.extend(episodes.drain(position..).map(|episode| (episode, "before --since-episode")));
+        }
+        
+        // Leave out episodes already transcribed, by the state file or an existing transcript,
+        // but retry those whose last attempt failed
+        if self.config.update {
+            let transcribed = read_transcribed_episodes(podcast_dir)?;
+            let (new, done): (Vec<_>, Vec<_>) = episodes.drain(..).partition(|episode| {
+                let paths = self.episode_paths(feed_title, podcast_dir, episode);
+                if paths.as_ref().is_some_and(ItemPaths::has_failed) {
+                    return true;
+                }
+                !transcribed.contains(&episode.state_id())
+                    && !paths.is_some_and(|paths| paths.transcript.exists())
+            });
+            info!("{} new episodes ({} already transcribed)", new.len(), done.len());
+            skipped.extend(done.into_iter().map(|episode| (episode, "already transcribed")));
+            *episodes = new;
+        }
+        
+        // Apply title filter and filter file rules
+        if self.config.has_item_filters() {
+            let total = episodes.len();
+            let (matching, excluded): (Vec<_>, Vec<_>) = episodes
+                .drain(..)
+                .partition(|episode| {
+                    self.config.accepts(&ItemMeta {
+                        title: &episode.title,
+                        duration: episode.duration_secs(),
+                        date: episode.pub_date.map(|date| date.date_naive()),
+                    })
+                });
+            info!(
+                "Filters excluded {} of {} episodes",
+                excluded.len(),
+                total
+            );
+            skipped.extend(excluded.into_iter().map(|episode| (episode, "filtered")));
+            *episodes = matching;
+        }
+        
+        if self.config.order == Order::Oldest {
+            episodes.reverse();
+        }
+        
+        if self.config.shuffle_items {
+            self.config.shuffle(episodes, feed_url);
+        }
+        
+        // Apply limit if specified. When resuming newest first, keep the episodes right
+        // after the reference rather than the newest ones so nothing is left in between.
+        let resuming = self.config.since_episode.is_some() && self.config.order == Order::Newest;
+        if let Some(limit) = self.config.limit {
+            if episodes.len() > limit {
+                info!("Limiting to {} episodes (out of {})", limit, episodes.len());
+                if resuming {
+                    episodes.drain(..episodes.len() - limit);
+                } else {
+                    episodes.truncate(limit);
+                }
+            }
+        }
+        
+        // Apply the duration cap from the same end as the limit
+        let durations: Vec<Option<f64>> = episodes.iter().map(PodcastEpisode::duration_secs).collect();
+        let within_cap = if resuming {
+            self.config.items_within_max_minutes(durations.into_iter().rev())
+        } else {
+            self.config.items_within_max_minutes(durations)
+        };
+        if let Some(count) = within_cap.filter(|&count| count < episodes.len()) {
+            info!(
+                "Limiting to {} episodes (out of {}) to stay within --max-minutes",
+                count,
+                episodes.len()
+            );
+            if resuming {
+                episodes.drain(..episodes.len() - count);
+            } else {
+                episodes.truncate(count);
+            }
+        }
+        
+        Ok(skipped)
+    }
+    
+    /// Where an episode's directory, transcript, and info file go
+    /// 
+    /// If another episode with the same title already has that directory (as
+    /// its info file shows), `--on-existing` decides; `None` means skip the
+    /// episode. Suffixed names use a short hash of the GUID, so they don't
+    /// depend on which episode was transcribed first.
+    fn episode_paths(&self, feed_title: &str, podcast_dir: &Path, episode: &PodcastEpisode) -> Option<ItemPaths> {
+        let naming = ItemNaming {
+            channel: feed_title,
+            title: &episode.title,
+            id: episode.guid.as_deref().unwrap_or(&episode.title),
+            date: episode.pub_date.map(|date| date.date_naive()),
+        };
+        self.config.claim_item_paths(
+            &episode.dir_name(),
+            &naming,
+            Some(&episode.id_hash()),
+            |dir_name, naming| self.config.item_paths(podcast_dir, dir_name, naming, "episode_info.txt"),
+            |info| is_other_episode(info, episode),
+        )
+    }
+    
+    /// Download and parse an RSS or Atom feed
+    async fn download_feed(&self, feed_url: &str) -> Result<(PodcastFeed, Vec<PodcastEpisode>)> {
+        debug!("Downloading feed: {}", feed_url);
         
         // Download feed
-        let response = reqwest::get(feed_url).await?;
+        let response = self
+            .config
+            .http_client
+            .get(feed_url)
+            .timeout(FEED_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
         let content = response.bytes().await?;
         
         // Parse feed
-        let channel = Channel::read_from(&content[..])?;
-        debug!("Found podcast: {} with {} items", channel.title, channel.items.len());
+        let (feed, episodes) = if is_atom_feed(&content) {
+            let atom = atom_syndication::Feed::read_from(&content[..])?;
+            debug!("Found Atom podcast: {} with {} entries", atom.title().as_str(), atom.entries().len());
+            (PodcastFeed::from_atom(&atom), self.extract_atom_episodes(&atom))
+        } else {
+            let channel = Channel::read_from(&content[..])?;
+            debug!("Found podcast: {} with {} items", channel.title, channel.items.len());
+            (PodcastFeed::from_rss(&channel), self.extract_episodes(&channel))
+        };
         
-        Ok(channel)
+        info!("Extracted {} episodes", episodes.len());
+        Ok((feed, episodes))
     }
     
     /// Create podcast directory
     fn create_podcast_directory(&self, podcast_title: &str) -> Result<PathBuf> {
-        let sanitized_title = utils::sanitize_filename(podcast_title);
-        let podcast_dir = self.config.output_dir.join(&sanitized_title);
+        let podcast_dir = self.config.source_dir(podcast_title);
         
         debug!("Creating podcast directory: {:?}", podcast_dir);
         fs::create_dir_all(&podcast_dir)?;
@@ -120,20 +542,20 @@ impl<'a> PodcastProcessor<'a> {
     }
     
     /// Save podcast information
-    fn save_podcast_info(&self, channel: &Channel, feed_url: &str, podcast_dir: &Path) -> Result<()> {
+    fn save_podcast_info(&self, feed: &PodcastFeed, feed_url: &str, podcast_dir: &Path) -> Result<()> {
         let info_file = podcast_dir.join("podcast_info.txt");
         
-        let mut info = format!("Title: {}\n", channel.title);
+        let mut info = format!("Title: {}\n", feed.title);
         info.push_str(&format!("Feed URL: {}\n", feed_url));
+        if self.config.include_description {
+            info.push_str(&format!("Description: {}\n", utils::clean_description(&feed.description)));
+        }
         
-        // Description is already a String, not an Option<String>
-        info.push_str(&format!("Description: {}\n", channel.description));
-        
-        if let Some(language) = &channel.language {
+        if let Some(language) = &feed.language {
             info.push_str(&format!("Language: {}\n", language));
         }
         
-        if let Some(author) = &channel.itunes_ext.as_ref().and_then(|ext| ext.author.clone()) {
+        if let Some(author) = &feed.author {
             info.push_str(&format!("Author: {}\n", author));
         }
         
@@ -144,18 +566,81 @@ impl<'a> PodcastProcessor<'a> {
         Ok(())
     }
     
-    /// Extract episodes from RSS feed
-    fn extract_episodes(&self, channel: &Channel) -> Result<Vec<PodcastEpisode>> {
-        let mut episodes = Vec::new();
+    /// Save episode information
+    fn save_episode_info(&self, episode: &PodcastEpisode, info_file: &Path) -> Result<()> {
+        let mut info = format!("Title: {}\n", episode.title);
+        info.push_str(&format!("Audio URL: {}\n", episode.audio_url));
         
-        for item in &channel.items {
-            if let Some(episode) = self.extract_episode(item) {
-                episodes.push(episode);
-            }
+        if let Some(guid) = &episode.guid {
+            info.push_str(&format!("GUID: {}\n", guid));
         }
         
-        info!("Extracted {} episodes", episodes.len());
-        Ok(episodes)
+        if let Some(season) = episode.season {
+            info.push_str(&format!("Season: {}\n", season));
+        }
+        
+        if let Some(number) = episode.episode {
+            info.push_str(&format!("Episode: {}\n", number));
+        }
+        
+        if let Some(pub_date) = episode.pub_date {
+            info.push_str(&format!("Published: {}\n", pub_date.to_rfc2822()));
+        }
+        
+        if let Some(duration) = episode.duration {
+            info.push_str(&format!("Duration: {} seconds\n", duration));
+        }
+        
+        fs::write(info_file, info)?;
+        debug!("Saved episode info to: {:?}", info_file);
+        
+        Ok(())
+    }
+    
+    /// Extract episodes from RSS feed
+    fn extract_episodes(&self, channel: &Channel) -> Vec<PodcastEpisode> {
+        channel
+            .items
+            .iter()
+            .filter_map(|item| self.extract_episode(item))
+            .collect()
+    }
+    
+    /// Extract episodes from Atom feed
+    fn extract_atom_episodes(&self, feed: &atom_syndication::Feed) -> Vec<PodcastEpisode> {
+        feed.entries()
+            .iter()
+            .filter_map(|entry| self.extract_atom_episode(entry))
+            .collect()
+    }
+    
+    /// Extract episode information from Atom entry
+    /// 
+    /// The audio comes from a `<link rel="enclosure">` that is audio by its
+    /// MIME type or, failing that, its file extension.
+    fn extract_atom_episode(&self, entry: &atom_syndication::Entry) -> Option<PodcastEpisode> {
+        let title = entry.title().as_str().trim().to_string();
+        
+        let audio_url = entry
+            .links()
+            .iter()
+            .find(|link| link.rel() == "enclosure" && is_audio_enclosure(link.mime_type(), link.href()))
+            .map(|link| link.href().to_string());
+        
+        if let Some(url) = audio_url {
+            Some(PodcastEpisode {
+                title,
+                audio_url: url,
+                pub_date: Some(*entry.published().unwrap_or(entry.updated())),
+                duration: None,
+                guid: Some(entry.id().to_string()),
+                season: None,
+                episode: None,
+            })
+        } else {
+            warn!("Skipping episode without audio enclosure: {}", title);
+            None
+        }
     }
     
     /// Extract episode information from RSS item
@@ -164,24 +649,43 @@ impl<'a> PodcastProcessor<'a> {
         let title = item.title.clone().unwrap_or_else(|| "Unknown Title".to_string());
         
         // Get audio URL
-        let audio_url = item.enclosure.as_ref().and_then(|enc| {
-            if enc.mime_type.starts_with("audio/") {
-                Some(enc.url.clone())
-            } else {
-                None
-            }
-        });
+        let audio_url = item
+            .enclosure
+            .as_ref()
+            .filter(|enc| is_audio_enclosure(Some(&enc.mime_type), &enc.url))
+            .map(|enc| enc.url.clone());
         
         // Get publication date
-        let pub_date = item.pub_date.as_ref().and_then(|date_str| {
-            DateTime::parse_from_rfc2822(date_str).ok()
-        });
+        let pub_date = item.pub_date.as_deref().and_then(parse_pub_date);
+        
+        // Get duration
+        let duration = item
+            .itunes_ext
+            .as_ref()
+            .and_then(|ext| ext.duration.as_deref())
+            .and_then(parse_itunes_duration);
+        
+        // Get season and episode numbers
+        let season = item
+            .itunes_ext
+            .as_ref()
+            .and_then(|ext| ext.season())
+            .and_then(|season| season.trim().parse().ok());
+        let episode = item
+            .itunes_ext
+            .as_ref()
+            .and_then(|ext| ext.episode())
+            .and_then(|episode| episode.trim().parse().ok());
         
         if let Some(url) = audio_url {
             Some(PodcastEpisode {
                 title,
                 audio_url: url,
                 pub_date,
+                duration,
+                guid: item.guid.as_ref().map(|guid| guid.value.clone()),
+                season,
+                episode,
             })
         } else {
             warn!("Skipping episode without audio enclosure: {}", title);
@@ -189,3 +693,309 @@ impl<'a> PodcastProcessor<'a> {
         }
     }
 }
+
+/// Whether an enclosure is audio, by its MIME type or, when that's missing or generic, its URL's extension
+/// 
+/// Many feeds label valid MP3s `application/octet-stream` or leave the type
+/// out, so those are judged by the file extension instead.
+fn is_audio_enclosure(mime_type: Option<&str>, url: &str) -> bool {
+    let mime_type = mime_type.unwrap_or_default().trim().to_lowercase();
+    if mime_type.starts_with("audio/") {
+        debug!("Enclosure {} is audio by its MIME type ({})", url, mime_type);
+        return true;
+    }
+    
+    let generic = mime_type.is_empty() || GENERIC_MIME_TYPES.contains(&mime_type.as_str());
+    if generic && utils::has_audio_extension(url) {
+        debug!("Enclosure {} is audio by its file extension (MIME type: '{}')", url, mime_type);
+        return true;
+    }
+    false
+}
+
+/// Whether downloaded feed content is an Atom feed rather than RSS
+/// 
+/// Looks at the name of the root element, skipping the XML declaration,
+/// comments, and doctype.
+fn is_atom_feed(content: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(&content[..content.len().min(4096)]);
+    let root = text
+        .match_indices('<')
+        .map(|(i, _)| &text[i + 1..])
+        .find(|tag| !tag.starts_with('?') && !tag.starts_with('!'));
+    
+    root.is_some_and(|tag| {
+        let name: String = tag.chars().take_while(|c| !c.is_whitespace() && *c != '>').collect();
+        name == "feed" || name.ends_with(":feed")
+    })
+}
+
+/// Parse a `<pubDate>` value
+/// 
+/// Tries RFC 2822 (what the RSS spec requires) first, then RFC 3339 / ISO 8601,
+/// then a few formats seen in feeds that don't follow the spec. Dates without a
+/// time zone are taken as UTC.
+fn parse_pub_date(date_str: &str) -> Option<DateTime<FixedOffset>> {
+    let date_str = date_str.trim();
+    
+    if let Ok(date) = DateTime::parse_from_rfc2822(date_str) {
+        debug!("Parsed publication date '{}' as RFC 2822", date_str);
+        return Some(date);
+    }
+    
+    if let Ok(date) = DateTime::parse_from_rfc3339(date_str) {
+        debug!("Parsed publication date '{}' as RFC 3339", date_str);
+        return Some(date);
+    }
+    
+    for format in PUB_DATE_FORMATS_WITH_ZONE {
+        if let Ok(date) = DateTime::parse_from_str(date_str, format) {
+            debug!("Parsed publication date '{}' with format '{}'", date_str, format);
+            return Some(date);
+        }
+    }
+    
+    for format in PUB_DATE_FORMATS_WITHOUT_ZONE {
+        if let Ok(date) = NaiveDateTime::parse_from_str(date_str, format) {
+            debug!("Parsed publication date '{}' with format '{}' (assuming UTC)", date_str, format);
+            return Some(date.and_utc().fixed_offset());
+        }
+    }
+    
+    if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        debug!("Parsed publication date '{}' as a plain date (assuming UTC midnight)", date_str);
+        return date.and_hms_opt(0, 0, 0).map(|date| date.and_utc().fixed_offset());
+    }
+    
+    debug!("Could not parse publication date '{}'", date_str);
+    None
+}
+
+/// Parse an `<itunes:duration>` value (`SS`, `MM:SS` or `HH:MM:SS`) into seconds
+fn parse_itunes_duration(duration: &str) -> Option<u64> {
+    duration
+        .trim()
+        .split(':')
+        .try_fold(0u64, |acc, part| {
+            // Some feeds use fractional seconds
+            let value = part.trim().parse::<f64>().ok()?;
+            Some(acc * 60 + value as u64)
+        })
+}
+
+/// Whether an existing episode info file was written for a different episode
+/// 
+/// Compares GUIDs, or audio URLs for info files written without one.
+fn is_other_episode(info: &str, episode: &PodcastEpisode) -> bool {
+    match (utils::info_field(info, "GUID"), episode.guid.as_deref()) {
+        (Some(existing), Some(guid)) => existing != guid,
+        _ => utils::info_field(info, "Audio URL").is_some_and(|url| url != episode.audio_url),
+    }
+}
+
+/// Episodes recorded as transcribed in a podcast directory's state file
+fn read_transcribed_episodes(podcast_dir: &Path) -> Result<HashSet<String>> {
+    let state_file = podcast_dir.join(TRANSCRIBED_EPISODES_FILE);
+    if !state_file.is_file() {
+        return Ok(HashSet::new());
+    }
+    
+    Ok(fs::read_to_string(&state_file)?
+        .lines()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Add a transcribed episode to the podcast directory's state file, for `--update`
+/// 
+/// Failing to record it only logs a warning.
+fn record_transcribed_episode(podcast_dir: &Path, episode: &PodcastEpisode) {
+    let state_file = podcast_dir.join(TRANSCRIBED_EPISODES_FILE);
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&state_file)
+        .and_then(|mut file| writeln!(file, "{}", episode.state_id()));
+    if let Err(e) = result {
+        warn!("Failed to record '{}' in {:?}: {}", episode.title, state_file, e);
+    }
+}
+
+/// Two-letter language code from a feed's `<language>`, e.g. `es` from `es-MX` or `EN_us`
+fn normalize_language_code(language: &str) -> Option<String> {
+    let code = language.trim().split(['-', '_']).next()?.to_lowercase();
+    (code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic())).then_some(code)
+}
+
+/// Parse an episode number like `42`, `E42`, or `S02E05` into (season, episode)
+fn parse_episode_number(reference: &str) -> Option<(Option<u32>, u32)> {
+    let pattern = Regex::new(r"(?i)^(?:S(\d+))?\s*E?(\d+)$").unwrap();
+    let captures = pattern.captures(reference)?;
+    let season = captures.get(1).and_then(|season| season.as_str().parse().ok());
+    let episode = captures[2].parse().ok()?;
+    Some((season, episode))
+}
+
+/// Find the position of the episode referred to by `--since-episode`
+/// 
+/// Errors if nothing matches, or if a bare episode number matches several seasons.
+fn find_episode(episodes: &[PodcastEpisode], reference: &str) -> Result<usize> {
+    let matches: Vec<usize> = episodes
+        .iter()
+        .enumerate()
+        .filter(|(_, episode)| episode.matches_reference(reference))
+        .map(|(i, _)| i)
+        .collect();
+    
+    match matches.as_slice() {
+        [] => Err(anyhow::anyhow!("No episode matches --since-episode '{}'", reference)),
+        [position] => Ok(*position),
+        _ => Err(anyhow::anyhow!(
+            "--since-episode '{}' matches {} episodes; use a GUID, full title, or SxxEyy",
+            reference,
+            matches.len()
+        )),
+    }
+}
+
+/// Heuristically detect feeds that only contain trailers or "subscribe" teasers
+fn is_preview_feed(episodes: &[PodcastEpisode]) -> bool {
+    if episodes.is_empty() || episodes.len() > PREVIEW_FEED_MAX_EPISODES {
+        return false;
+    }
+    
+    let trailer_pattern = Regex::new(
+        r"(?i)\b(trailer|preview|teaser|sneak peek|coming soon|subscribe|premium|bonus content|introducing)\b",
+    )
+    .unwrap();
+    
+    episodes.iter().all(|episode| {
+        trailer_pattern.is_match(&episode.title)
+            || episode.duration.is_some_and(|secs| secs < TRAILER_MAX_DURATION_SECS)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use tempfile::tempdir;
+    
+    fn episode(title: &str, guid: Option<&str>, audio_url: &str) -> PodcastEpisode {
+        PodcastEpisode {
+            title: title.to_string(),
+            audio_url: audio_url.to_string(),
+            pub_date: None,
+            duration: None,
+            guid: guid.map(str::to_string),
+            season: None,
+            episode: None,
+        }
+    }
+    
+    /// Claim an episode's paths and write its info file and transcript, as processing does
+    fn transcribe(processor: &PodcastProcessor, podcast_dir: &Path, episode: &PodcastEpisode) -> ItemPaths {
+        let paths = processor.episode_paths("Feed", podcast_dir, episode).unwrap();
+        fs::create_dir_all(&paths.dir).unwrap();
+        processor.save_episode_info(episode, &paths.info).unwrap();
+        fs::write(&paths.transcript, &episode.audio_url).unwrap();
+        paths
+    }
+    
+    #[test]
+    fn same_titled_episodes_keep_both_transcripts() {
+        let dir = tempdir().unwrap();
+        let config = Config::without_transcription(None, None, None, Some(dir.path()), &ConfigFile::default()).unwrap();
+        let processor = PodcastProcessor::new(&config);
+        let first = episode("Bonus", Some("guid-1"), "https://example.com/1.mp3");
+        let second = episode("Bonus", Some("guid-2"), "https://example.com/2.mp3");
+        
+        let first_paths = transcribe(&processor, dir.path(), &first);
+        let second_paths = transcribe(&processor, dir.path(), &second);
+        
+        assert_ne!(first_paths.dir, second_paths.dir);
+        assert!(second_paths.dir.ends_with(format!("Bonus_{}", second.id_hash())));
+        assert_eq!(fs::read_to_string(&first_paths.transcript).unwrap(), first.audio_url);
+        assert_eq!(fs::read_to_string(&second_paths.transcript).unwrap(), second.audio_url);
+        
+        // Each episode finds its own directory again on the next run
+        assert_eq!(processor.episode_paths("Feed", dir.path(), &first).unwrap().dir, first_paths.dir);
+        assert_eq!(processor.episode_paths("Feed", dir.path(), &second).unwrap().dir, second_paths.dir);
+    }
+    
+    #[tokio::test]
+    async fn gzipped_feeds_are_decoded() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            
+            let feed = include_bytes!("../tests/fixtures/feed.xml.gz");
+            let headers = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                feed.len()
+            );
+            stream.write_all(headers.as_bytes()).unwrap();
+            stream.write_all(feed).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        
+        let config = Config::without_transcription(None, None, None, None, &ConfigFile::default()).unwrap();
+        let (feed, episodes) = PodcastProcessor::new(&config).download_feed(&url).await.unwrap();
+        
+        let request = server.join().unwrap().to_lowercase();
+        assert!(request.contains("accept-encoding:") && request.contains("gzip"));
+        assert_eq!(feed.title, "Compressed Podcast");
+        let titles: Vec<&str> = episodes.iter().map(|episode| episode.title.as_str()).collect();
+        assert_eq!(titles, ["Episode Two", "Episode One"]);
+    }
+    
+    #[test]
+    fn atom_feeds_yield_episodes_with_audio_enclosures() {
+        let content = include_bytes!("../tests/fixtures/feed.atom");
+        assert!(is_atom_feed(content));
+        assert!(!is_atom_feed(b"<?xml version=\"1.0\"?>\n<rss version=\"2.0\"><channel></channel></rss>"));
+        
+        let atom = atom_syndication::Feed::read_from(&content[..]).unwrap();
+        let config = Config::without_transcription(None, None, None, None, &ConfigFile::default()).unwrap();
+        let episodes = PodcastProcessor::new(&config).extract_atom_episodes(&atom);
+        
+        // The entry without an audio enclosure is skipped
+        assert_eq!(episodes.len(), 2);
+        
+        let published = &episodes[0];
+        assert_eq!(published.title, "Published Episode");
+        assert_eq!(published.audio_url, "https://example.com/audio/1.mp3");
+        assert_eq!(published.guid.as_deref(), Some("urn:uuid:episode-1"));
+        assert_eq!(published.pub_date.unwrap().to_rfc3339(), "2024-01-01T09:30:00+00:00");
+        
+        // Recognized as audio by its extension, and dated by `updated` without `published`
+        let octet_stream = &episodes[1];
+        assert_eq!(octet_stream.title, "Octet-Stream Episode");
+        assert_eq!(octet_stream.audio_url, "https://example.com/audio/2.mp3");
+        assert_eq!(octet_stream.guid.as_deref(), Some("urn:uuid:episode-2"));
+        assert_eq!(octet_stream.pub_date.unwrap().to_rfc3339(), "2024-01-08T12:00:00+02:00");
+    }
+    
+    #[test]
+    fn other_episodes_are_told_apart_by_guid_then_audio_url() {
+        let info = "Title: Bonus\nAudio URL: https://example.com/1.mp3\nGUID: guid-1\n";
+        assert!(!is_other_episode(info, &episode("Bonus", Some("guid-1"), "https://example.com/moved.mp3")));
+        assert!(is_other_episode(info, &episode("Bonus", Some("guid-2"), "https://example.com/1.mp3")));
+        
+        let without_guid = "Title: Bonus\nAudio URL: https://example.com/1.mp3\n";
+        assert!(!is_other_episode(without_guid, &episode("Bonus", Some("guid-1"), "https://example.com/1.mp3")));
+        assert!(is_other_episode(without_guid, &episode("Bonus", None, "https://example.com/2.mp3")));
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use super::{SpeakerSegment, TranscribeOptions, Transcript, TranscriptionProvider};
+use crate::config::{Config, OutputFormat};
+
+/// AssemblyAI API base URL
+const ASSEMBLYAI_API_URL: &str = "https://api.assemblyai.com/v2";
+
+/// How often to poll for a finished transcript
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Most words in a single speaker segment built from speaker utterances
+const MAX_CUE_WORDS: usize = 12;
+
+/// AssemblyAI hosted transcription
+pub struct AssemblyAiProvider<'a> {
+    config: &'a Config,
+    client: reqwest::Client,
+}
+
+/// Response to an upload request
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    upload_url: String,
+}
+
+/// Transcript job status
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    id: String,
+    status: String,
+    text: Option<String>,
+    error: Option<String>,
+    /// Speaker turns, when `speaker_labels` was requested
+    utterances: Option<Vec<Utterance>>,
+    /// Detected language code, when `language_detection` was requested
+    language_code: Option<String>,
+}
+
+/// A single speaker turn
+#[derive(Debug, Deserialize)]
+struct Utterance {
+    /// Speaker label (`A`, `B`, ...)
+    speaker: String,
+    words: Vec<Word>,
+}
+
+/// A single word with its timing, in milliseconds
+#[derive(Debug, Deserialize)]
+struct Word {
+    text: String,
+    start: u64,
+    end: u64,
+}
+
+impl<'a> AssemblyAiProvider<'a> {
+    /// Create a new AssemblyAI provider
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            client: config.http_client.clone(),
+        }
+    }
+    
+    /// API key, checked when the config is created
+    fn api_key(&self) -> &str {
+        self.config.assemblyai_api_key.as_deref().unwrap_or_default()
+    }
+    
+    /// Upload the audio file and return its private URL
+    async fn upload(&self, audio: &Path) -> Result<String> {
+        debug!("Uploading {:?} to AssemblyAI", audio);
+        
+        let response = self
+            .client
+            .post(format!("{}/upload", ASSEMBLYAI_API_URL))
+            .header("authorization", self.api_key())
+            .body(fs::read(audio)?)
+            .send()
+            .await?
+            .error_for_status()
+            .context("AssemblyAI upload failed")?;
+        
+        Ok(response.json::<UploadResponse>().await?.upload_url)
+    }
+    
+    /// Start a transcript job for an uploaded file
+    async fn start_transcript(&self, audio_url: &str, opts: &TranscribeOptions<'_>) -> Result<String> {
+        let mut request = json!({ "audio_url": audio_url });
+        
+        match opts.language {
+            Some(language) => request["language_code"] = json!(language),
+            None => request["language_detection"] = json!(true),
+        }
+        
+        // Narrow detection down to the languages a code-switching recording mixes
+        if let (None, [primary, ..]) = (opts.language, opts.expected_languages) {
+            request["language_detection_options"] = json!({
+                "expected_languages": opts.expected_languages,
+                "fallback_language": primary,
+            });
+        }
+        
+        // AssemblyAI has no free-form prompt; the closest equivalent is boosting its words
+        if let Some(prompt) = opts.prompt {
+            let words: Vec<&str> = prompt.split_whitespace().collect();
+            request["word_boost"] = json!(words);
+        }
+        
+        if opts.speaker_labels {
+            request["speaker_labels"] = json!(true);
+        }
+        
+        let response = self
+            .client
+            .post(format!("{}/transcript", ASSEMBLYAI_API_URL))
+            .header("authorization", self.api_key())
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()
+            .context("Failed to start AssemblyAI transcript")?;
+        
+        Ok(response.json::<TranscriptResponse>().await?.id)
+    }
+    
+    /// Poll until the transcript job completes
+    async fn wait_for_transcript(&self, id: &str) -> Result<TranscriptResponse> {
+        loop {
+            let transcript: TranscriptResponse = self
+                .client
+                .get(format!("{}/transcript/{}", ASSEMBLYAI_API_URL, id))
+                .header("authorization", self.api_key())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            
+            match transcript.status.as_str() {
+                "completed" => return Ok(transcript),
+                "error" => {
+                    return Err(anyhow::anyhow!(
+                        "AssemblyAI transcription failed: {}",
+                        transcript.error.unwrap_or_default()
+                    ))
+                }
+                status => {
+                    debug!("AssemblyAI transcript {} is {}", id, status);
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+    
+    /// Fetch a completed transcript as subtitles (`srt` or `vtt`)
+    async fn fetch_subtitles(&self, id: &str, format: &str) -> Result<String> {
+        Ok(self
+            .client
+            .get(format!("{}/transcript/{}/{}", ASSEMBLYAI_API_URL, id, format))
+            .header("authorization", self.api_key())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for AssemblyAiProvider<'_> {
+    async fn transcribe(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        if opts.translate {
+            return Err(anyhow::anyhow!("AssemblyAI does not support translation"));
+        }
+        if opts.prompt.is_some() {
+            warn!("AssemblyAI doesn't support prompts; using the prompt's words as a vocabulary boost instead");
+        }
+        
+        info!("AssemblyAI transcription of file: {:?}", audio);
+        
+        let audio_url = self.upload(audio).await?;
+        let id = self.start_transcript(&audio_url, opts).await?;
+        let transcript = self.wait_for_transcript(&id).await?;
+        
+        let segments = opts
+            .speaker_labels
+            .then(|| speaker_segments(transcript.utterances.as_deref().unwrap_or_default()));
+        let text = match opts.format {
+            OutputFormat::Txt => transcript.text.unwrap_or_default(),
+            OutputFormat::Srt | OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => {
+                self.fetch_subtitles(&transcript.id, opts.format.response_format()).await?
+            }
+            OutputFormat::Json | OutputFormat::All => return Err(anyhow::anyhow!("JSON transcripts need the openai engine")),
+        };
+        
+        let language = opts.language.is_none().then_some(transcript.language_code).flatten();
+        
+        Ok(Transcript {
+            text,
+            segments,
+            language,
+            confidence: None,
+        })
+    }
+    
+    fn supports_speaker_labels(&self) -> bool {
+        true
+    }
+    
+    async fn check_api_key(&self) -> Result<()> {
+        let request = self
+            .client
+            .get(format!("{}/transcript?limit=1", ASSEMBLYAI_API_URL))
+            .header("authorization", self.api_key());
+        super::check_key_request("AssemblyAI", request).await
+    }
+}
+
+/// Build speaker segments from speaker turns
+/// 
+/// Long turns are broken into caption-sized segments of at most `MAX_CUE_WORDS` words.
+fn speaker_segments(utterances: &[Utterance]) -> Vec<SpeakerSegment> {
+    let mut segments = Vec::new();
+    
+    for utterance in utterances {
+        let speaker = speaker_name(&utterance.speaker);
+        for words in utterance.words.chunks(MAX_CUE_WORDS) {
+            let (Some(first), Some(last)) = (words.first(), words.last()) else {
+                continue;
+            };
+            let text: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+            segments.push(SpeakerSegment {
+                speaker: speaker.clone(),
+                start_ms: first.start,
+                end_ms: last.end,
+                text: text.join(" "),
+            });
+        }
+    }
+    
+    segments
+}
+
+/// Display name for an AssemblyAI speaker label, e.g. `A` -> `Speaker 1`
+fn speaker_name(label: &str) -> String {
+    match label.as_bytes() {
+        [letter @ b'A'..=b'Z'] => format!("Speaker {}", letter - b'A' + 1),
+        _ => format!("Speaker {}", label),
+    }
+}
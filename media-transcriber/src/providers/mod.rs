@@ -0,0 +1,156 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::config::{Config, Engine, OutputFormat};
+
+mod assemblyai;
+mod openai;
+mod whisper_cpp;
+
+pub use assemblyai::AssemblyAiProvider;
+pub use openai::OpenAiProvider;
+pub use whisper_cpp::WhisperCppProvider;
+
+/// Options for a single transcription call
+#[derive(Debug, Clone, Copy)]
+pub struct TranscribeOptions<'a> {
+    /// Language code, or `None` to let the provider detect it
+    pub language: Option<&'a str>,
+    /// Languages the audio may switch between when no language is given, most likely first
+    pub expected_languages: &'a [String],
+    /// Context to improve transcription accuracy
+    pub prompt: Option<&'a str>,
+    /// Output format of the returned transcript
+    pub format: OutputFormat,
+    /// Translate to English instead of transcribing in the spoken language
+    pub translate: bool,
+    /// Identify speakers, returning the transcript's speaker segments too
+    pub speaker_labels: bool,
+    /// Sampling temperature, or `None` for the provider default
+    pub temperature: Option<f32>,
+    /// Include word-level timestamps in JSON transcripts
+    pub word_timestamps: bool,
+    /// Model file to use instead of the configured one (whisper.cpp only)
+    pub model: Option<&'a Path>,
+}
+
+/// How long to wait for the API key check, so being offline doesn't hold up a run
+const KEY_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The service turned the API key down
+#[derive(Debug, Error)]
+#[error("API key rejected by {service} ({status})")]
+pub struct KeyRejected {
+    pub service: &'static str,
+    pub status: StatusCode,
+}
+
+/// A finished transcript
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    /// Transcript content in the requested format
+    pub text: String,
+    /// Speech attributed to each speaker, when speaker labels were requested
+    pub segments: Option<Vec<SpeakerSegment>>,
+    /// Spoken language as detected by the provider, when no language was given
+    pub language: Option<String>,
+    /// How sure the model was, when the provider reports it (OpenAI's `verbose_json`)
+    pub confidence: Option<Confidence>,
+}
+
+/// Average log probability below which a transcript is worth reviewing (Whisper's own retry threshold)
+const LOW_AVG_LOGPROB: f64 = -1.0;
+
+/// Chance of there being no speech above which a transcript is worth reviewing
+const HIGH_NO_SPEECH_PROB: f64 = 0.6;
+
+/// How sure the model was of a transcript, from Whisper's per-segment scores
+#[derive(Debug, Clone, Copy)]
+pub struct Confidence {
+    /// Average log probability of the transcribed tokens (closer to 0 is surer)
+    pub avg_logprob: f64,
+    /// Probability that the audio holds no speech at all
+    pub no_speech_prob: f64,
+    /// Seconds of audio the scores cover, for weighting when combining them
+    pub secs: f64,
+}
+
+impl Confidence {
+    /// Combine the scores of two parts of a recording, weighted by their length
+    pub fn merge(self, other: Confidence) -> Confidence {
+        let secs = self.secs + other.secs;
+        if secs <= 0.0 {
+            return self;
+        }
+        Confidence {
+            avg_logprob: (self.avg_logprob * self.secs + other.avg_logprob * other.secs) / secs,
+            no_speech_prob: (self.no_speech_prob * self.secs + other.no_speech_prob * other.secs) / secs,
+            secs,
+        }
+    }
+    
+    /// Whether the transcript is likely poor enough to need a look
+    pub fn is_low(&self) -> bool {
+        self.avg_logprob < LOW_AVG_LOGPROB || self.no_speech_prob > HIGH_NO_SPEECH_PROB
+    }
+}
+
+/// A stretch of speech by a single speaker
+#[derive(Debug, Clone)]
+pub struct SpeakerSegment {
+    /// Display name, e.g. `Speaker 1`
+    pub speaker: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// A backend that turns audio into text
+#[async_trait]
+pub trait TranscriptionProvider: Send + Sync {
+    /// Transcribe (or translate) a single audio file
+    async fn transcribe(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript>;
+    
+    /// Largest file the provider accepts in one call, or `None` if there's no limit
+    fn max_file_size(&self) -> Option<u64> {
+        None
+    }
+    
+    /// Whether the provider can tell speakers apart
+    fn supports_speaker_labels(&self) -> bool {
+        false
+    }
+    
+    /// Check the API key with a cheap authenticated request
+    /// 
+    /// Fails with [`KeyRejected`] if the service rejects the key. Providers
+    /// without an API key have nothing to check.
+    async fn check_api_key(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Send a key check request, turning 401/403 responses into [`KeyRejected`]
+async fn check_key_request(service: &'static str, request: reqwest::RequestBuilder) -> Result<()> {
+    let status = request.timeout(KEY_CHECK_TIMEOUT).send().await?.status();
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err(KeyRejected { service, status }.into());
+    }
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("{} key check returned {}", service, status));
+    }
+    Ok(())
+}
+
+/// Create the provider selected in the config
+pub fn from_config(config: &Config) -> Box<dyn TranscriptionProvider + '_> {
+    match config.engine {
+        Engine::OpenAi => Box::new(OpenAiProvider::new(config)),
+        Engine::WhisperCpp => Box::new(WhisperCppProvider::new(config)),
+        Engine::AssemblyAi => Box::new(AssemblyAiProvider::new(config)),
+    }
+}
@@ -0,0 +1,435 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use log::{info, warn};
+use regex::Regex;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{multipart, RequestBuilder, StatusCode};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use tempfile::tempdir;
+use thiserror::Error;
+
+use super::{Confidence, TranscribeOptions, Transcript, TranscriptionProvider};
+use crate::config::{self, ApiType, Config, OutputFormat};
+use crate::json::{self, JsonTranscript};
+use crate::srt::{self, Cue};
+use crate::utils;
+use crate::vtt;
+
+/// OpenAI API the requests go to unless `--base-url` says otherwise
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Path of the endpoint for translating audio to English
+const TRANSLATIONS_PATH: &str = "audio/translations";
+
+/// Path of the endpoint for transcribing audio, used for whatever podscript can't do
+const TRANSCRIPTIONS_PATH: &str = "audio/transcriptions";
+
+/// Path of the endpoint listing models, used to check the API key
+const MODELS_PATH: &str = "models";
+
+/// Azure OpenAI REST API version requested with `--api-type azure`
+const AZURE_API_VERSION: &str = "2024-06-01";
+
+/// OpenAI's upload limit is 25MB
+const OPENAI_MAX_FILE_SIZE: u64 = 25 * 1024 * 1024;
+
+/// The API turned a request away with HTTP 429
+#[derive(Debug, Error)]
+#[error("OpenAI rate limit exceeded: {message}")]
+struct RateLimited {
+    /// How long the API asked us to wait, if it said
+    retry_after: Option<Duration>,
+    message: String,
+}
+
+/// `verbose_json` transcription response, which includes the detected language
+#[derive(Debug, Deserialize)]
+struct VerboseTranscription {
+    text: String,
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    /// Only returned when word timestamps were asked for
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+}
+
+/// A timed segment of a `verbose_json` response, in seconds
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    #[serde(default)]
+    no_speech_prob: Option<f64>,
+}
+
+/// A timed word of a `verbose_json` response, in seconds
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+impl VerboseTranscription {
+    /// Render the transcript in the requested format from its text, segments, and words
+    fn render(&self, format: OutputFormat) -> Result<String> {
+        let cues: Vec<Cue> = self
+            .segments
+            .iter()
+            .map(|segment| Cue {
+                start_ms: (segment.start * 1000.0).round() as u64,
+                end_ms: (segment.end * 1000.0).round() as u64,
+                text: segment.text.trim().to_string(),
+            })
+            .collect();
+        
+        Ok(match format {
+            OutputFormat::Txt => self.text.clone(),
+            OutputFormat::Srt => srt::render_from(&cues, 1),
+            OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => vtt::render(&cues),
+            OutputFormat::Json | OutputFormat::All => self.to_json().render()?,
+        })
+    }
+    
+    /// Length-weighted averages of the segments' scores, if the response has them
+    fn confidence(&self) -> Option<Confidence> {
+        self.segments
+            .iter()
+            .filter_map(|segment| {
+                Some(Confidence {
+                    avg_logprob: segment.avg_logprob?,
+                    no_speech_prob: segment.no_speech_prob?,
+                    secs: (segment.end - segment.start).max(0.0),
+                })
+            })
+            .reduce(Confidence::merge)
+    }
+    
+    /// The response as a `--format json` transcript
+    fn to_json(&self) -> JsonTranscript {
+        JsonTranscript {
+            text: self.text.trim().to_string(),
+            language: self.language.clone(),
+            segments: self
+                .segments
+                .iter()
+                .map(|segment| json::Segment {
+                    start: segment.start,
+                    end: segment.end,
+                    text: segment.text.trim().to_string(),
+                })
+                .collect(),
+            words: self
+                .words
+                .iter()
+                .map(|word| json::Word {
+                    word: word.word.trim().to_string(),
+                    start: word.start,
+                    end: word.end,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// OpenAI Whisper API, via podscript for transcription
+pub struct OpenAiProvider<'a> {
+    config: &'a Config,
+}
+
+impl<'a> OpenAiProvider<'a> {
+    /// Create a new OpenAI provider
+    pub fn new(config: &'a Config) -> Self {
+        Self { config }
+    }
+    
+    /// Transcribe with the podscript binary
+    fn transcribe_with_podscript(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        info!("Direct transcription of file: {:?}", audio);
+        
+        // podscript writes its result to a file
+        let temp_dir = tempdir()?;
+        let output_file = temp_dir.path().join(format!("transcript.{}", opts.format.extension()));
+        
+        // Without a language, ask for verbose_json, the only format that says which language was detected
+        let detect_language = opts.language.is_none();
+        let response_format = if detect_language {
+            "verbose_json"
+        } else {
+            opts.format.response_format()
+        };
+        
+        // Use podscript command for transcription
+        let mut args = vec![
+            "open-ai-whisper",
+            audio.to_str().unwrap(),
+            "--output", output_file.to_str().unwrap(),
+            "--response-format", response_format,
+        ];
+        
+        // Add language if provided
+        if let Some(lang) = opts.language {
+            args.extend_from_slice(&["--language", lang]);
+        }
+        
+        // Add prompt if provided
+        if let Some(prompt) = opts.prompt {
+            args.extend_from_slice(&["--prompt", prompt]);
+        }
+        
+        let temperature = opts.temperature.map(|temperature| temperature.to_string());
+        if let Some(temperature) = &temperature {
+            args.extend_from_slice(&["--temperature", temperature]);
+        }
+        
+        // Set environment variable for API key
+        let mut command = Command::new(&self.config.podscript_binary);
+        command.args(&args)
+               .env("OPENAI_API_KEY", &self.config.api_key);
+        
+        // podscript picks the proxy up from the standard environment variables
+        if let Some(proxy) = &self.config.proxy {
+            command.env("HTTPS_PROXY", proxy).env("HTTP_PROXY", proxy);
+        }
+        
+        let output = self.config.command_runner.output(&mut command)?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if is_rate_limit_message(&stderr) {
+                return Err(RateLimited {
+                    retry_after: suggested_wait(&stderr),
+                    message: stderr.trim().to_string(),
+                }
+                .into());
+            }
+            return Err(anyhow::anyhow!("Transcription failed: {}", stderr));
+        }
+        
+        let output = fs::read_to_string(&output_file)?;
+        if response_format == "verbose_json" {
+            let transcription: VerboseTranscription = serde_json::from_str(&output)?;
+            return Ok(Transcript {
+                text: transcription.render(opts.format)?,
+                segments: None,
+                confidence: transcription.confidence(),
+                language: transcription.language,
+            });
+        }
+        
+        Ok(Transcript {
+            text: output,
+            segments: None,
+            language: None,
+            confidence: None,
+        })
+    }
+    
+    /// Translate to English with the OpenAI API
+    /// 
+    /// podscript has no translation command, so this calls the API directly.
+    /// OpenAI only translates with `whisper-1`, whatever `--model` says; other
+    /// endpoints get the configured model (or Azure deployment).
+    async fn translate(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        info!("Translating file to English: {:?}", audio);
+        let model = match self.config.openai_base_url {
+            Some(_) => &self.config.openai_model,
+            None => config::DEFAULT_OPENAI_MODEL,
+        };
+        self.call_api(TRANSLATIONS_PATH, model, audio, opts).await
+    }
+    
+    /// Transcribe with the OpenAI API, for models other than `whisper-1`
+    /// 
+    /// podscript always uses `whisper-1`. Newer models only return plain text
+    /// and don't report the language they detected.
+    async fn transcribe_with_api(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        info!("Transcribing file with {}: {:?}", self.config.openai_model, audio);
+        
+        let mut opts = *opts;
+        opts.format = OutputFormat::Txt;
+        self.call_api(TRANSCRIPTIONS_PATH, &self.config.openai_model, audio, &opts).await
+    }
+    
+    /// Transcribe with the API directly, in the requested format
+    /// 
+    /// podscript can't ask for word timestamps and only talks to api.openai.com,
+    /// so this is used for word timestamps and for `--base-url`.
+    async fn transcribe_directly(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        info!("Transcribing file with the API directly: {:?}", audio);
+        self.call_api(TRANSCRIPTIONS_PATH, &self.config.openai_model, audio, opts).await
+    }
+    
+    /// URL of the API endpoint at `path`, for `model` if it takes one
+    /// 
+    /// Azure addresses a model by its deployment in the path instead of the
+    /// request body, and wants the API version in the query.
+    fn endpoint(&self, path: &str, model: Option<&str>) -> String {
+        let base = self.config.openai_base_url.as_deref().unwrap_or(OPENAI_BASE_URL);
+        match (self.config.api_type, model) {
+            (ApiType::OpenAi, _) => format!("{}/{}", base, path),
+            (ApiType::Azure, Some(model)) => {
+                format!("{}/openai/deployments/{}/{}?api-version={}", base, model, path, AZURE_API_VERSION)
+            }
+            (ApiType::Azure, None) => format!("{}/openai/{}?api-version={}", base, path, AZURE_API_VERSION),
+        }
+    }
+    
+    /// Add the API key to a request: a bearer token for OpenAI, an `api-key` header for Azure
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.config.api_type {
+            ApiType::OpenAi => request.bearer_auth(&self.config.api_key),
+            ApiType::Azure => request.header("api-key", &self.config.api_key),
+        }
+    }
+    
+    /// Upload audio to the API endpoint at `path` and return the response as the transcript
+    /// 
+    /// JSON transcripts are built from the `verbose_json` response; other
+    /// formats are returned as the API sent them.
+    async fn call_api(&self, path: &str, model: &str, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        // OpenAI infers the audio format from the file name
+        let file_name = audio
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("audio.mp3")
+            .to_string();
+        let file_part = multipart::Part::bytes(fs::read(audio)?).file_name(file_name);
+        
+        let mut form = multipart::Form::new()
+            .part("file", file_part)
+            .text("model", model.to_string())
+            .text("response_format", opts.format.response_format());
+        
+        // Translations are always into English, so only transcriptions take a language
+        if let Some(language) = opts.language.filter(|_| !opts.translate) {
+            form = form.text("language", language.to_string());
+        }
+        
+        if let Some(prompt) = opts.prompt {
+            form = form.text("prompt", prompt.to_string());
+        }
+        
+        if let Some(temperature) = opts.temperature {
+            form = form.text("temperature", temperature.to_string());
+        }
+        
+        // Asking for words drops segments unless they're asked for too; translations only have segments
+        if opts.word_timestamps && !opts.translate {
+            form = form
+                .text("timestamp_granularities[]", "word")
+                .text("timestamp_granularities[]", "segment");
+        }
+        
+        let request = self.config.http_client.post(self.endpoint(path, Some(model)));
+        let response = self.authorize(request).multipart(form).send().await?;
+        
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .map(Duration::from_secs_f64);
+        let body = response.text().await?;
+        
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(RateLimited {
+                retry_after: retry_after.or_else(|| suggested_wait(&body)),
+                message: body,
+            }
+            .into());
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("OpenAI request failed ({}): {}", status, body));
+        }
+        
+        if opts.format == OutputFormat::Json {
+            let transcription: VerboseTranscription = serde_json::from_str(&body)?;
+            return Ok(Transcript {
+                text: transcription.render(opts.format)?,
+                segments: None,
+                confidence: transcription.confidence(),
+                language: transcription.language,
+            });
+        }
+        
+        Ok(Transcript {
+            text: body,
+            segments: None,
+            language: None,
+            confidence: None,
+        })
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAiProvider<'_> {
+    /// Transcribe or translate, backing off and retrying while the API is rate-limiting us
+    /// 
+    /// Waits as long as the API asks (from `Retry-After` or the error message), or
+    /// with exponential backoff otherwise, up to `rate_limit_retries` times.
+    async fn transcribe(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        let max_retries = self.config.rate_limit_retries;
+        let mut attempt = 0;
+        loop {
+            let result = if opts.translate {
+                self.translate(audio, opts).await
+            } else if self.config.openai_base_url.is_none() && self.config.openai_model != config::DEFAULT_OPENAI_MODEL {
+                self.transcribe_with_api(audio, opts).await
+            } else if opts.word_timestamps || self.config.openai_base_url.is_some() {
+                self.transcribe_directly(audio, opts).await
+            } else {
+                self.transcribe_with_podscript(audio, opts)
+            };
+            
+            let retry_after = match &result {
+                Err(e) if attempt < max_retries => match e.downcast_ref::<RateLimited>() {
+                    Some(limited) => limited.retry_after,
+                    None => return result,
+                },
+                _ => return result,
+            };
+            
+            attempt += 1;
+            let delay = retry_after.unwrap_or_else(|| utils::backoff_delay(attempt));
+            warn!(
+                "OpenAI rate limit hit for {:?}, retrying in {:?} (attempt {}/{})",
+                audio, delay, attempt, max_retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+    
+    fn max_file_size(&self) -> Option<u64> {
+        Some(OPENAI_MAX_FILE_SIZE)
+    }
+    
+    async fn check_api_key(&self) -> Result<()> {
+        let request = self.config.http_client.get(self.endpoint(MODELS_PATH, None));
+        super::check_key_request("OpenAI", self.authorize(request)).await
+    }
+}
+
+/// Whether an error message from podscript or the API is about rate limiting
+fn is_rate_limit_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("429") || message.contains("rate limit")
+}
+
+/// Wait suggested in an OpenAI error message, e.g. "Please try again in 6.5s" or "in 350ms"
+fn suggested_wait(message: &str) -> Option<Duration> {
+    let pattern = Regex::new(r"(?i)try again in (\d+(?:\.\d+)?)(ms|s)\b").unwrap();
+    let captures = pattern.captures(message)?;
+    let amount: f64 = captures[1].parse().ok()?;
+    let secs = if captures[2].eq_ignore_ascii_case("ms") { amount / 1000.0 } else { amount };
+    Some(Duration::from_secs_f64(secs))
+}
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use log::info;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+use super::{TranscribeOptions, Transcript, TranscriptionProvider};
+use crate::config::{Config, OutputFormat};
+
+/// Local whisper.cpp binary
+pub struct WhisperCppProvider<'a> {
+    config: &'a Config,
+}
+
+impl<'a> WhisperCppProvider<'a> {
+    /// Create a new whisper.cpp provider
+    pub fn new(config: &'a Config) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for WhisperCppProvider<'_> {
+    async fn transcribe(&self, audio: &Path, opts: &TranscribeOptions<'_>) -> Result<Transcript> {
+        info!("Local whisper.cpp transcription of file: {:?}", audio);
+        
+        let model = match opts.model {
+            Some(model) => model,
+            None => self
+                .config
+                .whisper_model
+                .as_deref()
+                .context("--whisper-model is required for the whispercpp engine")?,
+        };
+        
+        // whisper.cpp only reliably reads 16kHz mono WAV
+        let temp_dir = tempdir()?;
+        let wav_file = temp_dir.path().join("audio.wav");
+        let threads_str = self.config.transcode_threads.map(|threads| threads.to_string());
+        let mut ffmpeg_args = vec![
+            "-nostdin", "-v", "quiet", "-y",
+            "-i", audio.to_str().unwrap(),
+            "-ar", "16000", "-ac", "1",
+            "-c:a", "pcm_s16le",
+        ];
+        if let Some(threads) = &threads_str {
+            ffmpeg_args.extend_from_slice(&["-threads", threads]);
+        }
+        ffmpeg_args.push(wav_file.to_str().unwrap());
+        self.config.command_runner.run("ffmpeg", &ffmpeg_args)?;
+        
+        // whisper.cpp appends the extension to the output base path itself
+        let output_base = temp_dir.path().join("transcript");
+        let format_flag = match opts.format {
+            OutputFormat::Txt => "--output-txt",
+            OutputFormat::Srt => "--output-srt",
+            OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => "--output-vtt",
+            OutputFormat::Json | OutputFormat::All => return Err(anyhow::anyhow!("JSON transcripts need the openai engine")),
+        };
+        
+        let mut args = vec![
+            "--model", model.to_str().unwrap(),
+            "--file", wav_file.to_str().unwrap(),
+            "--output-file", output_base.to_str().unwrap(),
+            format_flag,
+            "--no-prints",
+            // whisper.cpp defaults to English rather than detecting the language
+            "--language", opts.language.unwrap_or("auto"),
+        ];
+        
+        if opts.translate {
+            args.push("--translate");
+        }
+        
+        // The JSON output is the only place whisper.cpp reports the language it detected
+        let detect_language = opts.language.is_none();
+        if detect_language {
+            args.push("--output-json");
+        }
+        
+        // Add prompt if provided
+        if let Some(prompt) = opts.prompt {
+            args.extend_from_slice(&["--prompt", prompt]);
+        }
+        
+        let temperature = opts.temperature.map(|temperature| temperature.to_string());
+        if let Some(temperature) = &temperature {
+            args.extend_from_slice(&["--temperature", temperature]);
+        }
+        
+        self.config.command_runner.run(&self.config.whisper_cpp_binary, &args)
+            .context("whisper.cpp transcription failed")?;
+        
+        let language = if detect_language {
+            detected_language(&output_base.with_extension("json"))
+        } else {
+            None
+        };
+        
+        Ok(Transcript {
+            text: fs::read_to_string(output_base.with_extension(opts.format.extension()))?,
+            segments: None,
+            language,
+            confidence: None,
+        })
+    }
+}
+
+/// Language whisper.cpp detected, from the `result.language` field of its JSON output
+fn detected_language(json_file: &Path) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(json_file).ok()?).ok()?;
+    json["result"]["language"].as_str().map(str::to_string)
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use log::debug;
+use std::io;
+use std::process::{Command, Output};
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+use crate::utils;
+
+/// Runs the external programs the tool calls (yt-dlp, ffmpeg, ffprobe, ...)
+/// 
+/// Code that assembles command lines takes a runner rather than spawning
+/// processes itself, so it can be given a stand-in that records the commands
+/// instead of needing the programs installed.
+pub trait CommandRunner: Send + Sync {
+    /// Run a command to completion and capture its output
+    fn output(&self, command: &mut Command) -> io::Result<Output>;
+    
+    /// Run a program with arguments, returning its stdout, or an error if it exits unsuccessfully
+    fn run(&self, program: &str, args: &[&str]) -> Result<String> {
+        debug!("Running command: {} {:?}", program, args);
+        
+        let output = self.output(Command::new(program).args(args))?;
+        
+        if output.status.success() {
+            Ok(String::from_utf8(output.stdout)?)
+        } else {
+            Err(anyhow::anyhow!(
+                "Command failed with exit code {}: {}",
+                output.status.code().unwrap_or(-1),
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        }
+    }
+}
+
+/// Runs commands for real, honouring the current item's `--item-timeout` deadline
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        utils::command_output(command)
+    }
+}
+
+/// Records the commands it's given instead of running them, answering each with the same stdout
+/// 
+/// Clones share the record, so a test can keep one while the config owns another.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct RecordingRunner {
+    stdout: String,
+    commands: Arc<Mutex<Vec<Vec<String>>>>,
+}
+
+#[cfg(test)]
+impl RecordingRunner {
+    /// Runner whose commands all succeed and print `stdout`
+    pub fn new(stdout: &str) -> Self {
+        Self {
+            stdout: stdout.to_string(),
+            ..Default::default()
+        }
+    }
+    
+    /// Commands run so far, each as the program followed by its arguments
+    pub fn commands(&self) -> Vec<Vec<String>> {
+        self.commands.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for RecordingRunner {
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        let line = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        self.commands.lock().unwrap().push(line);
+        
+        Ok(Output {
+            status: Default::default(),
+            stdout: self.stdout.clone().into_bytes(),
+            stderr: Vec::new(),
+        })
+    }
+}
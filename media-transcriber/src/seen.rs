@@ -0,0 +1,76 @@
+use anyhow::Result;
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// File in `--cache-dir` listing transcribed YouTube video IDs, one per line
+pub const SEEN_VIDEOS_FILE: &str = "seen_videos.txt";
+
+/// YouTube video IDs already handled, so overlapping sources don't transcribe a video twice
+/// 
+/// IDs are remembered for the whole run. With `--cache-dir`, successfully
+/// transcribed IDs are also saved there, so later runs skip them too.
+#[derive(Debug, Default)]
+pub struct SeenVideos {
+    ids: Mutex<HashSet<String>>,
+    /// Where transcribed IDs are saved, if persisting
+    file: Option<PathBuf>,
+}
+
+impl SeenVideos {
+    /// Start with the IDs saved in `cache_dir` by earlier runs, if given
+    pub fn load(cache_dir: Option<&Path>) -> Result<Self> {
+        let Some(cache_dir) = cache_dir else {
+            return Ok(Self::default());
+        };
+        
+        let file = cache_dir.join(SEEN_VIDEOS_FILE);
+        let ids: HashSet<String> = if file.is_file() {
+            fs::read_to_string(&file)?
+                .lines()
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        debug!("Loaded {} seen video IDs from {:?}", ids.len(), file);
+        
+        Ok(Self {
+            ids: Mutex::new(ids),
+            file: Some(file),
+        })
+    }
+    
+    /// Whether a video was already handled
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.lock().unwrap().contains(id)
+    }
+    
+    /// Remember a video for the rest of the run, returning `false` if it was already seen
+    pub fn insert(&self, id: &str) -> bool {
+        self.ids.lock().unwrap().insert(id.to_string())
+    }
+    
+    /// Save a transcribed video's ID for later runs, if `--cache-dir` is set
+    /// 
+    /// Failing to save only logs a warning.
+    pub fn persist(&self, id: &str) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        
+        let result = file
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(file))
+            .and_then(|mut f| writeln!(f, "{}", id));
+        if let Err(e) = result {
+            warn!("Failed to save video ID {} to {:?}: {}", id, file, e);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+
+/// A single SRT subtitle cue
+#[derive(Debug, Clone)]
+pub struct Cue {
+    /// Start time in milliseconds
+    pub start_ms: u64,
+    /// End time in milliseconds
+    pub end_ms: u64,
+    /// Cue text (may span multiple lines)
+    pub text: String,
+}
+
+/// Parse SRT content into cues
+///
+/// Cue numbers are ignored since they are regenerated on render. Lines
+/// starting with `#` are treated as comments and skipped.
+pub fn parse(content: &str) -> Result<Vec<Cue>> {
+    let mut cues = Vec::new();
+    let normalized = content.replace("\r\n", "\n");
+    
+    for block in normalized.split("\n\n") {
+        let lines: Vec<&str> = block
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect();
+        
+        // Find the timing line; it's usually the second line after the cue number
+        let Some(timing_index) = lines.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+        
+        let (start, end) = lines[timing_index]
+            .split_once("-->")
+            .context("Invalid SRT timing line")?;
+        
+        cues.push(Cue {
+            start_ms: parse_timestamp(start.trim())?,
+            end_ms: parse_timestamp(end.trim())?,
+            text: lines[timing_index + 1..].join("\n"),
+        });
+    }
+    
+    Ok(cues)
+}
+
+/// Render cues as SRT content, numbering them from `first_number`
+pub fn render_from(cues: &[Cue], first_number: usize) -> String {
+    let mut output = String::new();
+    
+    for (i, cue) in cues.iter().enumerate() {
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            first_number + i,
+            format_timestamp(cue.start_ms),
+            format_timestamp(cue.end_ms),
+            cue.text
+        ));
+    }
+    
+    output
+}
+
+/// Shift all cues forward by the given offset
+pub fn shift(cues: &mut [Cue], offset_ms: u64) {
+    for cue in cues {
+        cue.start_ms += offset_ms;
+        cue.end_ms += offset_ms;
+    }
+}
+
+/// Parse an SRT timestamp (`HH:MM:SS,mmm`) into milliseconds
+fn parse_timestamp(timestamp: &str) -> Result<u64> {
+    let (hms, millis) = timestamp
+        .split_once([',', '.'])
+        .unwrap_or((timestamp, "0"));
+    
+    let parts: Vec<u64> = hms
+        .split(':')
+        .map(|part| part.trim().parse::<u64>())
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("Invalid SRT timestamp: {}", timestamp))?;
+    
+    let seconds = parts.iter().fold(0, |acc, part| acc * 60 + part);
+    let millis: u64 = millis
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid SRT timestamp: {}", timestamp))?;
+    
+    Ok(seconds * 1000 + millis)
+}
+
+/// Format milliseconds as an SRT timestamp (`HH:MM:SS,mmm`)
+pub fn format_timestamp(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        ms % 1000
+    )
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::manifest::{ItemStatus, ManifestItem};
+use crate::utils;
+
+/// Longest source shown in the summary table before it is shortened
+const MAX_SOURCE_WIDTH: usize = 60;
+
+/// Number of items per outcome
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ItemCounts {
+    pub transcribed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl ItemCounts {
+    /// Items counted since an earlier snapshot of the same tally
+    pub fn since(&self, earlier: &ItemCounts) -> ItemCounts {
+        ItemCounts {
+            transcribed: self.transcribed - earlier.transcribed,
+            skipped: self.skipped - earlier.skipped,
+            failed: self.failed - earlier.failed,
+        }
+    }
+}
+
+/// Running item counts for the whole run, updated whenever a manifest is written
+#[derive(Debug, Default)]
+pub struct ItemTally {
+    counts: Mutex<ItemCounts>,
+    /// Videos skipped as unavailable, by reason (private, deleted, ...)
+    unavailable: Mutex<BTreeMap<&'static str, usize>>,
+    /// Titles of items whose transcripts the model was unsure of
+    low_confidence: Mutex<Vec<String>>,
+}
+
+impl ItemTally {
+    /// Count the outcomes of a source's items
+    pub fn add(&self, items: &[ManifestItem]) {
+        let mut counts = self.counts.lock().unwrap();
+        for item in items {
+            match item.status {
+                ItemStatus::Success => counts.transcribed += 1,
+                ItemStatus::Skipped => counts.skipped += 1,
+                ItemStatus::Failed => counts.failed += 1,
+            }
+        }
+    }
+    
+    /// Current counts
+    pub fn snapshot(&self) -> ItemCounts {
+        *self.counts.lock().unwrap()
+    }
+    
+    /// Count a video skipped because it's unavailable for `reason`
+    pub fn add_unavailable(&self, reason: &'static str) {
+        *self.unavailable.lock().unwrap().entry(reason).or_default() += 1;
+    }
+    
+    /// Videos skipped as unavailable so far, by reason
+    pub fn unavailable(&self) -> BTreeMap<&'static str, usize> {
+        self.unavailable.lock().unwrap().clone()
+    }
+    
+    /// Flag an item whose transcript came back with low confidence
+    pub fn add_low_confidence(&self, title: &str) {
+        self.low_confidence.lock().unwrap().push(title.to_string());
+    }
+    
+    /// Items flagged with low confidence so far
+    pub fn low_confidence(&self) -> Vec<String> {
+        self.low_confidence.lock().unwrap().clone()
+    }
+}
+
+/// What happened to a single source
+#[derive(Debug)]
+struct SourceOutcome {
+    source: String,
+    items: ItemCounts,
+    /// Error that stopped the source from being processed at all
+    error: Option<String>,
+    /// Whether the error was that the source listed nothing to transcribe
+    empty: bool,
+}
+
+impl SourceOutcome {
+    /// A source failed if it errored out or any of its items failed
+    fn failed(&self) -> bool {
+        self.error.is_some() || self.items.failed > 0
+    }
+}
+
+/// Per-source outcomes of a run, printed as a table at the end
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    sources: Vec<SourceOutcome>,
+}
+
+impl RunSummary {
+    /// Record the outcome of a source
+    pub fn record(&mut self, source: &str, items: ItemCounts, result: &anyhow::Result<()>) {
+        self.sources.push(SourceOutcome {
+            source: source.to_string(),
+            items,
+            error: result.as_ref().err().map(|e| e.to_string()),
+            empty: result.as_ref().is_err_and(|e| e.is::<utils::NothingToTranscribe>()),
+        });
+    }
+    
+    /// Whether any source failed
+    pub fn has_failures(&self) -> bool {
+        self.sources.iter().any(SourceOutcome::failed)
+    }
+    
+    /// Print the summary table to stderr (stdout is reserved for events)
+    /// 
+    /// Unavailable videos are broken down by reason below the table, followed
+    /// by any low-confidence transcripts.
+    pub fn print(&self, tally: &ItemTally) {
+        let width = self
+            .sources
+            .iter()
+            .map(|outcome| outcome.source.chars().count().min(MAX_SOURCE_WIDTH))
+            .max()
+            .unwrap_or(0)
+            .max("Source".len());
+        
+        eprintln!();
+        eprintln!("{:<width$}  {:>11}  {:>7}  {:>6}  Status", "Source", "Transcribed", "Skipped", "Failed");
+        
+        let mut total = ItemCounts::default();
+        for outcome in &self.sources {
+            let status = match (&outcome.error, outcome.items.failed) {
+                (Some(error), _) if outcome.empty => format!("empty: {}", error),
+                (Some(error), _) => format!("error: {}", error),
+                (None, 0) => "ok".to_string(),
+                (None, _) => "partial".to_string(),
+            };
+            eprintln!(
+                "{:<width$}  {:>11}  {:>7}  {:>6}  {}",
+                shorten(&outcome.source),
+                outcome.items.transcribed,
+                outcome.items.skipped,
+                outcome.items.failed,
+                status
+            );
+            
+            total.transcribed += outcome.items.transcribed;
+            total.skipped += outcome.items.skipped;
+            total.failed += outcome.items.failed;
+        }
+        
+        let failed_sources = self.sources.iter().filter(|outcome| outcome.failed()).count();
+        eprintln!(
+            "{:<width$}  {:>11}  {:>7}  {:>6}  {}/{} sources failed",
+            "Total",
+            total.transcribed,
+            total.skipped,
+            total.failed,
+            failed_sources,
+            self.sources.len()
+        );
+        
+        let unavailable = tally.unavailable();
+        if !unavailable.is_empty() {
+            let reasons: Vec<String> = unavailable.iter().map(|(reason, count)| format!("{} {}", count, reason)).collect();
+            eprintln!("Skipped unavailable videos: {}", reasons.join(", "));
+        }
+        
+        let low_confidence = tally.low_confidence();
+        if !low_confidence.is_empty() {
+            eprintln!("Low-confidence transcripts to review ({}):", low_confidence.len());
+            for title in low_confidence {
+                eprintln!("  {}", title);
+            }
+        }
+    }
+}
+
+/// Shorten a source to fit the table, keeping its end (usually the most specific part)
+fn shorten(source: &str) -> String {
+    let len = source.chars().count();
+    if len <= MAX_SOURCE_WIDTH {
+        return source.to_string();
+    }
+    
+    let tail: String = source.chars().skip(len - (MAX_SOURCE_WIDTH - 3)).collect();
+    format!("...{}", tail)
+}
@@ -0,0 +1,260 @@
+use chrono::NaiveDate;
+use log::{debug, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::config::OutputFormat;
+use crate::utils;
+
+/// File name `--keep-audio` uses inside an item directory
+pub const AUDIO_FILE_NAME: &str = "audio.mp3";
+
+/// File name recording why an item failed, inside its item directory
+pub const ERROR_FILE_NAME: &str = "transcribe_error.txt";
+
+/// A value that can be substituted into an output template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Title,
+    Channel,
+    Date,
+    Year,
+    Month,
+    Id,
+    Ext,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "title" => Some(Self::Title),
+            "channel" => Some(Self::Channel),
+            "date" => Some(Self::Date),
+            "year" => Some(Self::Year),
+            "month" => Some(Self::Month),
+            "id" => Some(Self::Id),
+            "ext" => Some(Self::Ext),
+            _ => None,
+        }
+    }
+}
+
+/// Literal text or a placeholder within a path component
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// Values an item's placeholders resolve to
+#[derive(Debug, Clone, Copy)]
+pub struct ItemNaming<'a> {
+    /// Podcast, channel, or `Local Files`
+    pub channel: &'a str,
+    pub title: &'a str,
+    /// GUID, video ID, or file name
+    pub id: &'a str,
+    /// Publication/upload date (modification date for local files), when known
+    pub date: Option<NaiveDate>,
+}
+
+/// Where an item's files are written
+#[derive(Debug, Clone)]
+pub struct ItemPaths {
+    /// Directory to create before writing
+    pub dir: PathBuf,
+    /// Transcript file
+    pub transcript: PathBuf,
+    /// Info file with the item's metadata
+    pub info: PathBuf,
+    /// Where `--keep-audio` copies the audio, as `.mp3` (with the extension swapped for other formats)
+    pub audio: PathBuf,
+    /// Error details from the last failed attempt, removed once the item succeeds
+    pub error: PathBuf,
+}
+
+impl ItemPaths {
+    /// Write the error and when it happened, marking the item for a retry
+    /// 
+    /// Failing to write the file only logs a warning, so it never hides the
+    /// original error.
+    pub fn record_failure(&self, error: &anyhow::Error) {
+        let details = format!("Failed: {}\nError: {:#}\n", chrono::Local::now().to_rfc3339(), error);
+        match fs::write(&self.error, details) {
+            Ok(()) => debug!("Recorded failure in {:?}", self.error),
+            Err(e) => warn!("Failed to write {:?}: {}", self.error, e),
+        }
+    }
+    
+    /// Remove the error file left by an earlier failed attempt, if any
+    pub fn clear_failure(&self) {
+        if self.has_failed() {
+            if let Err(e) = fs::remove_file(&self.error) {
+                warn!("Failed to remove {:?}: {}", self.error, e);
+            }
+        }
+    }
+    
+    /// Whether the last attempt at the item failed
+    /// 
+    /// A transcript next to the error file may be incomplete, so the item
+    /// should be retried rather than counted as transcribed.
+    pub fn has_failed(&self) -> bool {
+        self.error.is_file()
+    }
+}
+
+/// Layout of transcripts under the output directory, e.g. `{channel}/{year}/{title}`
+/// 
+/// Each `/`-separated component becomes a directory, except that a last
+/// component containing `{ext}` names the transcript file itself. Otherwise
+/// the transcript is written as `transcript.<ext>` inside the last directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    components: Vec<Vec<Part>>,
+}
+
+impl FromStr for OutputTemplate {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components = s
+            .split(['/', '\\'])
+            .filter(|component| !component.is_empty())
+            .map(parse_component)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        if components.is_empty() {
+            return Err("Output template is empty".to_string());
+        }
+        if s.starts_with(['/', '\\']) || s.contains(':') {
+            return Err(format!("Output template must be a relative path: '{}'", s));
+        }
+        if let Some((_, parents)) = components.split_last() {
+            if parents.iter().any(|parts| parts.contains(&Part::Placeholder(Placeholder::Ext))) {
+                return Err("{ext} can only be used in the last component of an output template".to_string());
+            }
+        }
+        
+        Ok(Self { components })
+    }
+}
+
+/// Parse one path component into literal text and placeholders
+fn parse_component(component: &str) -> Result<Vec<Part>, String> {
+    if component == "." || component == ".." {
+        return Err(format!("Output template components can't be '{}'", component));
+    }
+    
+    let mut parts = Vec::new();
+    let mut rest = component;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            parts.push(Part::Literal(rest[..open].to_string()));
+        }
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed placeholder in '{}'", component))?;
+        let name = &rest[open + 1..open + close];
+        let placeholder = Placeholder::from_name(name).ok_or_else(|| {
+            format!(
+                "Unknown placeholder {{{}}} (expected title, channel, date, year, month, id, or ext)",
+                name
+            )
+        })?;
+        parts.push(Part::Placeholder(placeholder));
+        rest = &rest[open + close + 1..];
+    }
+    if rest.contains('}') {
+        return Err(format!("Unmatched '}}' in '{}'", component));
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest.to_string()));
+    }
+    
+    Ok(parts)
+}
+
+impl OutputTemplate {
+    /// Directory for a source's own files (manifest, podcast/channel info)
+    /// 
+    /// This is the leading directories that depend on nothing but `{channel}`,
+    /// or the output directory itself when the template starts with item fields.
+    pub fn source_dir(&self, output_dir: &Path, channel: &str) -> PathBuf {
+        let naming = ItemNaming {
+            channel,
+            title: "",
+            id: "",
+            date: None,
+        };
+        let (_, parents) = self.components.split_last().expect("template has components");
+        
+        let mut dir = output_dir.to_path_buf();
+        for parts in parents {
+            let channel_only = parts.iter().all(|part| {
+                matches!(part, Part::Literal(_) | Part::Placeholder(Placeholder::Channel))
+            });
+            if !channel_only {
+                break;
+            }
+            dir.push(resolve_component(parts, &naming, OutputFormat::Txt));
+        }
+        dir
+    }
+    
+    /// Paths of an item's directory, transcript, and info file (named `info_name` in directory layouts)
+    pub fn item_paths(&self, output_dir: &Path, naming: &ItemNaming, format: OutputFormat, info_name: &str) -> ItemPaths {
+        let (last, parents) = self.components.split_last().expect("template has components");
+        
+        let mut parent = output_dir.to_path_buf();
+        for parts in parents {
+            parent.push(resolve_component(parts, naming, format));
+        }
+        let name = resolve_component(last, naming, format);
+        
+        if last.contains(&Part::Placeholder(Placeholder::Ext)) {
+            let dir = utils::long_path_safe(&parent);
+            let transcript = dir.join(&name);
+            ItemPaths {
+                info: transcript.with_extension("info.txt"),
+                audio: transcript.with_extension("mp3"),
+                error: transcript.with_extension("error.txt"),
+                transcript,
+                dir,
+            }
+        } else {
+            let dir = utils::item_dir(&parent, &name);
+            ItemPaths {
+                transcript: dir.join(format.transcript_file_name()),
+                info: dir.join(info_name),
+                audio: dir.join(AUDIO_FILE_NAME),
+                error: dir.join(ERROR_FILE_NAME),
+                dir,
+            }
+        }
+    }
+}
+
+/// Fill in a component's placeholders, sanitizing each substituted value on its own
+fn resolve_component(parts: &[Part], naming: &ItemNaming, format: OutputFormat) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::Literal(text) => text.clone(),
+            Part::Placeholder(Placeholder::Title) => utils::sanitize_filename(naming.title),
+            Part::Placeholder(Placeholder::Channel) => utils::sanitize_filename(naming.channel),
+            Part::Placeholder(Placeholder::Id) => utils::sanitize_filename(naming.id),
+            Part::Placeholder(Placeholder::Date) => naming
+                .date
+                .map_or("unknown_date".to_string(), |date| date.format("%Y-%m-%d").to_string()),
+            Part::Placeholder(Placeholder::Year) => naming
+                .date
+                .map_or("unknown_date".to_string(), |date| date.format("%Y").to_string()),
+            Part::Placeholder(Placeholder::Month) => naming
+                .date
+                .map_or("unknown_date".to_string(), |date| date.format("%m").to_string()),
+            Part::Placeholder(Placeholder::Ext) => format.extension().to_string(),
+        })
+        .collect()
+}
@@ -1,116 +1,472 @@
 use anyhow::Result;
-use log::{debug, info};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
 use tempfile::tempdir;
 
-use crate::config::Config;
+use crate::cleanup::Cleaner;
+use crate::config::{Config, OutputFormat, RetryAdjustment};
+use crate::events::Event;
+use crate::json::{self, JsonTranscript};
+use crate::providers::{self, Confidence, SpeakerSegment, TranscribeOptions, TranscriptionProvider};
+use crate::srt;
 use crate::utils;
+use crate::vtt;
+use crate::writer::{self, OutputWriter, TranscriptMeta};
+
+/// Length of each chunk when the provider has no upload limit to size chunks by, in seconds
+const DEFAULT_CHUNK_DURATION_SECS: u64 = 1000;
+
+/// Share of the provider's upload limit a chunk may use, leaving room for MP3 framing
+const CHUNK_SIZE_BUDGET: f64 = 0.95;
+
+/// Most words a repeated fragment at a chunk seam is expected to span
+const MAX_SEAM_WORDS: usize = 60;
+
+/// Fewest matching words treated as a repeated fragment rather than coincidence
+const MIN_SEAM_WORDS: usize = 3;
+
+/// Leading words of a chunk that may be a half-cut word from the overlap start
+const MAX_SEAM_PARTIAL_WORDS: usize = 2;
+
+/// Length of the clip transcribed by `--detect-language-only`, in seconds
+const LANGUAGE_CLIP_SECS: u64 = 30;
 
 /// Transcription service for audio files
+/// 
+/// Handles chunking, stitching, and output files; the actual speech-to-text
+/// call is delegated to the configured provider.
 pub struct TranscriptionService<'a> {
     config: &'a Config,
+    provider: Box<dyn TranscriptionProvider + 'a>,
+    /// Language to use when none is configured, e.g. the one a feed declares
+    default_language: Option<String>,
+    /// Chapters known from the source's metadata (e.g. YouTube chapters)
+    chapters: Vec<utils::Chapter>,
+    /// Language the provider detected in the file being transcribed
+    detected_language: Mutex<Option<String>>,
+    /// The model's confidence in the file being transcribed, combined over its chunks
+    confidence: Mutex<Option<Confidence>>,
 }
 
-/// Transcription request parameters
-#[derive(Debug, Serialize)]
-struct TranscriptionRequest {
-    file: PathBuf,
-    model: String,
-    language: Option<String>,
-    prompt: Option<String>,
-    response_format: String,
-    temperature: f32,
-}
-
-/// Transcription response
-#[derive(Debug, Deserialize)]
-struct TranscriptionResponse {
-    text: String,
+/// What to produce from the audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Task {
+    /// Transcript in the spoken language
+    Transcribe,
+    /// English translation
+    Translate,
 }
 
 impl<'a> TranscriptionService<'a> {
     /// Create a new transcription service
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        let provider = providers::from_config(config);
+        if config.speaker_labels && !provider.supports_speaker_labels() {
+            warn!("The {:?} engine can't tell speakers apart; writing plain VTT without voice tags", config.engine);
+        }
+        if config.diarize && !provider.supports_speaker_labels() {
+            warn!("The {:?} engine can't tell speakers apart; ignoring --diarize", config.engine);
+        }
+        
+        Self {
+            config,
+            provider,
+            default_language: None,
+            chapters: Vec::new(),
+            detected_language: Mutex::new(None),
+            confidence: Mutex::new(None),
+        }
+    }
+    
+    /// Fall back to `language` when neither `--language` nor a language segment applies
+    pub fn with_default_language(mut self, language: Option<String>) -> Self {
+        self.default_language = language;
+        self
+    }
+    
+    /// Mark chapters in the transcript, as headings for text and title cues for SRT/VTT
+    /// 
+    /// Text transcripts are transcribed chapter by chapter to know where each
+    /// heading goes. Chapters are ignored when only part of the file is transcribed.
+    pub fn with_chapters(mut self, chapters: Vec<utils::Chapter>) -> Self {
+        self.chapters = chapters;
+        self
+    }
+    
+    /// Language for a time range of the audio, falling back to the default language
+    fn language_for_range(&self, start: u64, end: u64) -> Option<&str> {
+        self.config
+            .language_for_range(start, end)
+            .or(self.default_language.as_deref())
     }
     
     /// Transcribe an audio file
-    pub async fn transcribe_file(&self, audio_file: &Path, output_file: &Path) -> Result<()> {
+    /// 
+    /// With `translate_also`, `output_file` is used as a template and both
+    /// `transcript.<lang>.<ext>` and `transcript.en.<ext>` are written instead.
+    /// `title` and `source` head HTML transcripts.
+    pub async fn transcribe_file(&self, audio_file: &Path, output_file: &Path, title: &str, source: &str) -> Result<()> {
         info!("Transcribing audio file: {:?}", audio_file);
         
         // Check if file exists
         if !audio_file.exists() {
             return Err(anyhow::anyhow!("Audio file does not exist: {:?}", audio_file));
         }
+        *self.detected_language.lock().unwrap() = None;
+        *self.confidence.lock().unwrap() = None;
         
-        // Check file size
-        let file_size = fs::metadata(audio_file)?.len();
-        debug!("Audio file size: {} bytes", file_size);
+        // Cut out the requested time range first, so later steps only see that part
+        let range_dir = (self.config.range_start.is_some() || self.config.range_end.is_some())
+            .then(tempdir)
+            .transpose()?;
+        let audio_file = match &range_dir {
+            Some(dir) => {
+                let range_file = dir.path().join("range.mp3");
+                self.cut_time_range(audio_file, &range_file)?;
+                range_file
+            }
+            None => audio_file.to_path_buf(),
+        };
+        let audio_file = audio_file.as_path();
+        
+        // Even out the volume first, if asked to
+        let normalized_dir = self.config.normalize.then(tempdir).transpose()?;
+        let audio_file = match &normalized_dir {
+            Some(dir) => {
+                let normalized_file = dir.path().join("normalized.mp3");
+                info!("Normalizing loudness of {:?}", audio_file);
+                utils::normalize_audio(&*self.config.command_runner, audio_file, &normalized_file, self.config.transcode_threads)?;
+                normalized_file
+            }
+            None => audio_file.to_path_buf(),
+        };
+        let audio_file = audio_file.as_path();
+        
+        // Work out which outputs to produce
+        let task = if self.config.translate { Task::Translate } else { Task::Transcribe };
+        let mut outputs = vec![(task, self.transcript_path(output_file))];
+        if self.config.translate_also {
+            outputs.push((Task::Translate, language_tagged_path(output_file, "en")));
+        }
         
-        // OpenAI's limit is 25MB
-        const MAX_SIZE: u64 = 25 * 1024 * 1024;
+        // Chapters from the metadata don't line up with a cut-out time range
+        let chapters: &[utils::Chapter] = if self.config.range_start.is_some() || self.config.range_end.is_some() {
+            &[]
+        } else {
+            &self.chapters
+        };
         
-        if file_size <= MAX_SIZE {
+        // Text has no timestamps, so headings can only go in if chapters are transcribed separately
+        if chapters.len() > 1 && self.format() == OutputFormat::Txt {
+            self.transcribe_chapters(audio_file, &outputs, chapters, source).await?;
+            return self.finish_outputs(&outputs, chapters, title, source);
+        }
+        
+        // Prefer cutting at embedded chapter markers, if asked to
+        if self.config.split_by_chapters {
+            let embedded = utils::audio_chapters(&*self.config.command_runner, audio_file)?;
+            if embedded.len() > 1 {
+                self.transcribe_chapters(audio_file, &outputs, &embedded, source).await?;
+                return self.finish_outputs(&outputs, chapters, title, source);
+            }
+            debug!("No chapters found in {:?}, transcribing as a whole", audio_file);
+        }
+        
+        if self.fits_upload_limit(audio_file)? {
             // File is small enough, transcribe directly
-            self.transcribe_single_file(audio_file, output_file).await?;
+            self.config.emit(Event::TranscribeProgress {
+                file: audio_file,
+                chunk: 1,
+                chunks: 1,
+            });
+            let language = self.language_for_range(0, u64::MAX);
+            for (task, path) in &outputs {
+                self.run_task(*task, audio_file, path, language).await?;
+            }
         } else {
             // File is too large, split and transcribe in chunks
-            self.transcribe_large_file(audio_file, output_file).await?;
+            self.transcribe_large_file(audio_file, &outputs, 0).await?;
+        }
+        
+        self.finish_outputs(&outputs, chapters, title, source)
+    }
+    
+    /// Format transcripts are produced in, which is WebVTT for HTML and Markdown output until it's rendered
+    fn format(&self) -> OutputFormat {
+        self.config.format.transcribed_as()
+    }
+    
+    /// Write finished outputs in their final format, then copy the transcript to stdout if asked
+    fn finish_outputs(&self, outputs: &[(Task, PathBuf)], chapters: &[utils::Chapter], title: &str, source: &str) -> Result<()> {
+        for (task, path) in outputs {
+            self.finish_output(*task, path, chapters, title, source)?;
+        }
+        self.write_to_stdout(&outputs[0].1)
+    }
+    
+    /// Read a finished output and write it through the writers for `--format`, cleaning it first if
+    /// `--clean` is set (and writing the raw version next to it with `--keep-raw`)
+    fn finish_output(&self, task: Task, path: &Path, chapters: &[utils::Chapter], title: &str, source: &str) -> Result<()> {
+        let mut transcript = self.read_transcript(path)?;
+        let meta = TranscriptMeta { title, source, chapters };
+        let writers = writer::writers_for(self.config.format);
+        
+        if self.config.clean {
+            if self.config.keep_raw {
+                write_outputs(&writers, &transcript, &meta, &language_tagged_path(path, "raw"))?;
+            }
+            self.cleaner(task).clean(&mut transcript);
+            debug!("Cleaned {:?}", path);
+        }
+        write_outputs(&writers, &transcript, &meta, path)
+    }
+    
+    /// Read a finished output in the format it was transcribed in
+    fn read_transcript(&self, path: &Path) -> Result<JsonTranscript> {
+        let content = fs::read_to_string(path)?;
+        match self.format() {
+            OutputFormat::Txt => Ok(JsonTranscript {
+                text: content,
+                ..Default::default()
+            }),
+            OutputFormat::Json => JsonTranscript::parse(&content),
+            _ => Ok(JsonTranscript::from_cues(&self.parse_cues(&content)?)),
+        }
+    }
+    
+    /// Cleaner for `--clean`, using the fillers of the output's language
+    fn cleaner(&self, task: Task) -> Cleaner {
+        let language = match task {
+            Task::Transcribe => self
+                .language_for_range(0, u64::MAX)
+                .map(str::to_string)
+                .or_else(|| self.detected_language()),
+            Task::Translate => Some("en".to_string()),
+        };
+        Cleaner::new(language.as_deref(), &self.config.filler_words)
+    }
+    
+    /// Copy a finished (original-language) transcript to stdout, if `--stdout` is set
+    fn write_to_stdout(&self, transcript_file: &Path) -> Result<()> {
+        if !self.config.stdout {
+            return Ok(());
         }
         
+        let transcript = fs::read_to_string(transcript_file)?;
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(transcript.as_bytes())?;
+        if !transcript.ends_with('\n') {
+            writeln!(stdout)?;
+        }
+        stdout.flush()?;
         Ok(())
     }
     
-    /// Transcribe a single audio file (less than 25MB)
-    async fn transcribe_single_file(&self, audio_file: &Path, output_file: &Path) -> Result<()> {
-        info!("Direct transcription of file: {:?}", audio_file);
+    /// Cut `--start`/`--end` out of a file, checking the range lies within the audio
+    fn cut_time_range(&self, audio_file: &Path, range_file: &Path) -> Result<()> {
+        let duration = utils::audio_duration(&*self.config.command_runner, audio_file)?;
+        let start = self.config.range_start.map_or(0.0, |start| start.secs);
+        let end = self.config.range_end.map_or(duration, |end| end.secs);
         
+        if start >= duration {
+            return Err(anyhow::anyhow!(
+                "--start ({}s) is not before the end of the audio ({})",
+                start,
+                utils::format_duration(Some(duration))
+            ));
+        }
+        if end > duration {
+            return Err(anyhow::anyhow!(
+                "--end ({}s) is past the end of the audio ({})",
+                end,
+                utils::format_duration(Some(duration))
+            ));
+        }
+        
+        info!(
+            "Transcribing only {} to {} of {:?}",
+            utils::format_duration(Some(start)),
+            utils::format_duration(Some(end)),
+            audio_file
+        );
+        utils::cut_audio(&*self.config.command_runner, audio_file, range_file, start, end, self.config.transcode_threads)
+    }
+    
+    /// Language detected in the last file transcribed, if no language was given and the provider reported one
+    /// 
+    /// For files transcribed in chunks or chapters, this is the first chunk's language.
+    pub fn detected_language(&self) -> Option<String> {
+        self.detected_language.lock().unwrap().clone()
+    }
+    
+    /// The model's confidence in the last file transcribed, if the provider reported it
+    pub fn confidence(&self) -> Option<Confidence> {
+        *self.confidence.lock().unwrap()
+    }
+    
+    /// Add the detected language and confidence of the last file transcribed to its info file
+    /// 
+    /// Low-confidence transcripts are also flagged for the end-of-run summary, under `title`.
+    pub fn record_detection(&self, info_file: &Path, title: &str) -> Result<()> {
+        if let Some(language) = self.detected_language() {
+            utils::record_detected_language(info_file, &language)?;
+        }
+        if let Some(confidence) = self.confidence() {
+            utils::record_confidence(info_file, &confidence)?;
+            if confidence.is_low() {
+                warn!(
+                    "Low confidence in the transcript of '{}' (average log probability {:.2}, no-speech probability {:.2})",
+                    title, confidence.avg_logprob, confidence.no_speech_prob
+                );
+                self.config.item_tally.add_low_confidence(title);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Detect the spoken language from the first seconds of a local file or URL, without transcribing it all
+    pub async fn detect_language(&self, source: &str) -> Result<Option<String>> {
+        let temp_dir = tempdir()?;
+        let clip = temp_dir.path().join("clip.mp3");
+        utils::extract_clip(&*self.config.command_runner, source, &clip, LANGUAGE_CLIP_SECS)?;
+        
+        let opts = TranscribeOptions {
+            language: None,
+            expected_languages: &self.config.expected_languages,
+            prompt: None,
+            format: OutputFormat::Txt,
+            translate: false,
+            speaker_labels: false,
+            temperature: None,
+            word_timestamps: false,
+            model: None,
+        };
+        Ok(self.provider.transcribe(&clip, &opts).await?.language)
+    }
+    
+    /// Detect an item's language for `--detect-language-only`, logging it and adding it to the info file
+    pub async fn report_language(&self, title: &str, source: &str, info_file: &Path) -> Result<()> {
+        match self.detect_language(source).await? {
+            Some(language) => {
+                info!("Detected language of '{}': {}", title, language);
+                utils::record_detected_language(info_file, &language)
+            }
+            None => {
+                warn!("The {:?} engine didn't report a language for '{}'", self.config.engine, title);
+                Ok(())
+            }
+        }
+    }
+    
+    /// Path the original-language transcript is written to for a given output file
+    pub fn transcript_path(&self, output_file: &Path) -> PathBuf {
+        transcript_path(self.config, self.default_language.as_deref(), output_file)
+    }
+    
+    /// Whether a file is small enough for the provider to take in one request
+    fn fits_upload_limit(&self, audio_file: &Path) -> Result<bool> {
+        let file_size = fs::metadata(audio_file)?.len();
+        debug!("Audio file size: {} bytes", file_size);
+        
+        // Providers with an upload cap need large files split into chunks
+        Ok(self.provider.max_file_size().is_none_or(|max_size| file_size <= max_size))
+    }
+    
+    /// Run a single task on an audio file that fits within the upload limit
+    async fn run_task(
+        &self,
+        task: Task,
+        audio_file: &Path,
+        output_file: &Path,
+        language: Option<&str>,
+    ) -> Result<()> {
         // Create output directory if it doesn't exist
         if let Some(parent) = output_file.parent() {
             fs::create_dir_all(parent)?;
         }
         
-        // Use podscript command for transcription
-        let mut args = vec![
-            "open-ai-whisper",
-            audio_file.to_str().unwrap(),
-            "--output", output_file.to_str().unwrap(),
-        ];
+        let mut opts = TranscribeOptions {
+            language,
+            expected_languages: &self.config.expected_languages,
+            prompt: self.config.prompt.as_deref(),
+            format: self.format(),
+            translate: task == Task::Translate,
+            speaker_labels: (self.config.speaker_labels || self.config.diarize)
+                && self.provider.supports_speaker_labels(),
+            temperature: self.config.temperature,
+            word_timestamps: self.config.word_timestamps,
+            model: None,
+        };
         
-        // Add language if provided
-        if let Some(lang) = &self.config.language {
-            args.extend_from_slice(&["--language", lang]);
-        }
+        let mut transcript = self.provider.transcribe(audio_file, &opts).await?;
         
-        // Add prompt if provided
-        if let Some(prompt) = &self.config.prompt {
-            args.extend_from_slice(&["--prompt", prompt]);
+        // Silence misdetection or a wrong language guess can produce nothing; retry once with adjusted settings
+        if !self.config.empty_retry.is_empty() && self.is_empty_transcript(&transcript.text) {
+            warn!("Transcript of {:?} came back empty, retrying with adjusted settings", audio_file);
+            for adjustment in &self.config.empty_retry {
+                match adjustment {
+                    RetryAdjustment::Temperature(temperature) => opts.temperature = Some(*temperature),
+                    RetryAdjustment::Language(language) => opts.language = Some(language),
+                    RetryAdjustment::Model(model) => opts.model = Some(model),
+                    RetryAdjustment::NoPrompt => opts.prompt = None,
+                }
+            }
+            
+            transcript = self.provider.transcribe(audio_file, &opts).await?;
+            if self.is_empty_transcript(&transcript.text) {
+                return Err(anyhow::anyhow!("Transcript of {:?} is still empty after retrying", audio_file));
+            }
         }
         
-        // Set environment variable for API key
-        // Use the podscript binary from the parent directory
-        let mut command = Command::new("../podscript");
-        command.args(&args)
-               .env("OPENAI_API_KEY", &self.config.api_key);
+        if let Some(language) = transcript.language.filter(|_| task == Task::Transcribe) {
+            let mut detected = self.detected_language.lock().unwrap();
+            if detected.is_none() {
+                info!("Detected language of {:?}: {}", audio_file, language);
+                *detected = Some(language);
+            }
+        }
+        if let Some(confidence) = transcript.confidence.filter(|_| task == Task::Transcribe) {
+            let mut combined = self.confidence.lock().unwrap();
+            *combined = Some(match *combined {
+                Some(earlier) => earlier.merge(confidence),
+                None => confidence,
+            });
+        }
         
-        let output = command.output()?;
+        // Attribute the text to speakers, if the provider told them apart
+        let text = match &transcript.segments {
+            Some(segments) if !segments.is_empty() => self.render_speaker_segments(segments)?,
+            _ => transcript.text,
+        };
         
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Transcription failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+        fs::write(output_file, text.trim())?;
         
         info!("Transcription completed successfully: {:?}", output_file);
         Ok(())
     }
     
     /// Transcribe a large audio file by splitting it into chunks
-    async fn transcribe_large_file(&self, audio_file: &Path, output_file: &Path) -> Result<()> {
+    /// 
+    /// The file is split once and every requested output is produced from the same chunks.
+    /// Chunks overlap by `chunk_overlap_secs`, and whatever was transcribed twice at a
+    /// seam is dropped from the later chunk when stitching. `offset` is where the file
+    /// starts within the original recording, in seconds, and is only used to pick
+    /// per-segment languages.
+    /// 
+    /// Progress is checkpointed after every chunk, so if a previous attempt failed
+    /// part-way, transcription resumes from the first chunk it didn't finish.
+    async fn transcribe_large_file(
+        &self,
+        audio_file: &Path,
+        outputs: &[(Task, PathBuf)],
+        offset: u64,
+    ) -> Result<()> {
         info!("Splitting and transcribing large file: {:?}", audio_file);
         
         // Create temporary directory for chunks
@@ -121,31 +477,593 @@ impl<'a> TranscriptionService<'a> {
         fs::create_dir_all(&chunks_dir)?;
         fs::create_dir_all(&transcripts_dir)?;
         
-        // Split audio file into chunks (20MB each)
-        let chunk_files = utils::split_audio_file(audio_file, &chunks_dir, 1000)?;
+        // Split audio file into chunks that fit the upload limit
+        let (chunk_duration, bitrate_kbps) = self.chunk_plan(audio_file)?;
+        let chunk_files = utils::split_audio_file(
+            &*self.config.command_runner,
+            audio_file,
+            &chunks_dir,
+            chunk_duration,
+            self.config.chunk_overlap_secs,
+            &[],
+            bitrate_kbps,
+            self.config.transcode_threads,
+        )?;
+        
+        // Transcribe each chunk, once per output, appending to the outputs as we go
+        let mut stitched = outputs
+            .iter()
+            .map(|(_, path)| StitchedOutput::resume(path, self.format(), chunk_files.len()))
+            .collect::<Result<Vec<_>>>()?;
         
-        // Transcribe each chunk
-        let mut all_transcripts = String::new();
+        let chunks_done = stitched.iter().map(|output| output.chunks_done).min().unwrap_or(0);
+        let progress = self.chunk_progress(chunk_files.len(), chunks_done)?;
         
         for (i, chunk_file) in chunk_files.iter().enumerate() {
-            let transcript_file = transcripts_dir.join(format!("transcript_{}.txt", i + 1));
+            if stitched.iter().all(|output| output.chunks_done > i) {
+                debug!("Chunk {}/{} was transcribed by a previous run", i + 1, chunk_files.len());
+                continue;
+            }
+            
+            // Pick the language for this chunk's time range
+            let chunk_start = i as u64 * chunk_duration;
+            let audio_start = chunk_start.saturating_sub(self.config.chunk_overlap_secs);
+            let language = self.language_for_range(offset + chunk_start, offset + chunk_start + chunk_duration);
             
             info!("Transcribing chunk {}/{}", i + 1, chunk_files.len());
-            self.transcribe_single_file(chunk_file, &transcript_file).await?;
+            self.config.emit(Event::TranscribeProgress {
+                file: audio_file,
+                chunk: i + 1,
+                chunks: chunk_files.len(),
+            });
             
-            // Read transcript and append to combined transcript
-            let transcript = fs::read_to_string(&transcript_file)?;
-            all_transcripts.push_str(&transcript);
-            all_transcripts.push_str("\n\n");
+            for (j, (task, _)) in outputs.iter().enumerate() {
+                if stitched[j].chunks_done > i {
+                    continue;
+                }
+                
+                let transcript_file = transcripts_dir.join(format!(
+                    "transcript_{}_{}.{}",
+                    i + 1,
+                    j + 1,
+                    self.format().extension()
+                ));
+                self.run_task(*task, chunk_file, &transcript_file, language).await?;
+                
+                // Read transcript and append to combined transcript
+                let transcript = fs::read_to_string(&transcript_file)?;
+                match self.format() {
+                    OutputFormat::Txt => stitched[j].append_text(&transcript)?,
+                    OutputFormat::Srt | OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => {
+                        // Chunk timestamps start at zero, so shift them to the chunk's position
+                        let mut cues = self.parse_cues(&transcript)?;
+                        srt::shift(&mut cues, audio_start * 1000);
+                        stitched[j].append_cues(cues)?;
+                    }
+                    OutputFormat::Json | OutputFormat::All => {
+                        let mut json = JsonTranscript::parse(&transcript)?;
+                        json.shift(audio_start as f64);
+                        stitched[j].append_json(json)?;
+                    }
+                }
+                
+                // The chunk's transcript is now in the output; don't keep it around
+                fs::remove_file(&transcript_file)?;
+                stitched[j].checkpoint(chunk_files.len())?;
+            }
+            progress.inc(1);
         }
+        progress.finish();
         
-        // Write combined transcript to output file
-        if let Some(parent) = output_file.parent() {
+        for output in stitched {
+            output.finish()?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Progress bar across the chunks of a large file, with elapsed and estimated time
+    /// 
+    /// Starts at the chunks a previous run already transcribed, so the
+    /// estimate only counts the ones left. Hidden with `--quiet`.
+    fn chunk_progress(&self, chunks: usize, chunks_done: usize) -> Result<ProgressBar> {
+        let progress = ProgressBar::new(chunks as u64);
+        progress.set_style(
+            ProgressStyle::with_template(
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} chunks ({eta})",
+            )?
+            .progress_chars("=> "),
+        );
+        if self.config.quiet {
+            progress.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        progress.set_position(chunks_done as u64);
+        progress.reset_eta();
+        
+        // Chunks take a while, so keep the spinner and elapsed time moving in between
+        progress.enable_steady_tick(Duration::from_millis(100));
+        Ok(progress)
+    }
+    
+    /// Chunk length in seconds and MP3 bitrate in kbps for splitting a large file
+    /// 
+    /// Chunks are encoded no higher than the source's own bitrate or
+    /// `--chunk-bitrate`, and made as long as the provider's upload limit
+    /// allows at that bitrate (overlap included), unless `--chunk-seconds`
+    /// fixes the length.
+    fn chunk_plan(&self, audio_file: &Path) -> Result<(u64, u32)> {
+        let duration = utils::audio_duration(&*self.config.command_runner, audio_file)?;
+        let source_kbps = fs::metadata(audio_file)?.len() as f64 * 8.0 / duration.max(1.0) / 1000.0;
+        let bitrate_kbps = utils::chunk_bitrate(source_kbps, self.config.chunk_bitrate_kbps);
+        let bytes_per_sec = bitrate_kbps as f64 * 1000.0 / 8.0;
+        
+        let chunk_duration = match (self.config.chunk_secs, self.provider.max_file_size()) {
+            (Some(secs), max_size) => {
+                let chunk_bytes = (secs + self.config.chunk_overlap_secs) as f64 * bytes_per_sec;
+                if max_size.is_some_and(|max_size| chunk_bytes > max_size as f64 * CHUNK_SIZE_BUDGET) {
+                    warn!(
+                        "--chunk-seconds {} makes chunks of about {:.1}MB at {}kbps, which may exceed the upload limit",
+                        secs, chunk_bytes / 1024.0 / 1024.0, bitrate_kbps
+                    );
+                }
+                secs
+            }
+            (None, Some(max_size)) => {
+                let fitting_secs = (max_size as f64 * CHUNK_SIZE_BUDGET / bytes_per_sec) as u64;
+                fitting_secs.saturating_sub(self.config.chunk_overlap_secs).max(1)
+            }
+            (None, None) => DEFAULT_CHUNK_DURATION_SECS,
+        };
+        
+        debug!(
+            "Source bitrate ~{:.0}kbps; splitting into {}s chunks at {}kbps",
+            source_kbps, chunk_duration, bitrate_kbps
+        );
+        Ok((chunk_duration, bitrate_kbps))
+    }
+    
+    /// Transcribe a file with embedded chapters, writing one transcript per chapter
+    /// 
+    /// Chapter transcripts go to `chapters/NN_Title/` next to each output, and
+    /// the combined transcript is still written to the output itself.
+    async fn transcribe_chapters(
+        &self,
+        audio_file: &Path,
+        outputs: &[(Task, PathBuf)],
+        chapters: &[utils::Chapter],
+        source: &str,
+    ) -> Result<()> {
+        info!("Splitting {:?} into {} chapters", audio_file, chapters.len());
+        
+        let temp_dir = tempdir()?;
+        let chapter_files = utils::split_audio_file(
+            &*self.config.command_runner,
+            audio_file,
+            &temp_dir.path().join("chapters"),
+            DEFAULT_CHUNK_DURATION_SECS,
+            0,
+            chapters,
+            self.config.chunk_bitrate_kbps,
+            self.config.transcode_threads,
+        )?;
+        
+        let mut stitched = outputs
+            .iter()
+            .map(|(_, path)| StitchedOutput::create(path, self.format()))
+            .collect::<Result<Vec<_>>>()?;
+        
+        for (i, (chapter, chapter_file)) in chapters.iter().zip(&chapter_files).enumerate() {
+            let title = chapter.title.clone().unwrap_or_else(|| format!("Chapter {}", i + 1));
+            
+            info!("Transcribing chapter {}/{}: {}", i + 1, chapters.len(), title);
+            self.config.emit(Event::TranscribeProgress {
+                file: audio_file,
+                chunk: i + 1,
+                chunks: chapters.len(),
+            });
+            
+            let chapter_outputs: Vec<(Task, PathBuf)> = outputs
+                .iter()
+                .map(|(task, path)| (*task, chapter_path(path, i + 1, &title)))
+                .collect();
+            
+            // Long chapters may still need chunking
+            let (start, end) = (chapter.start as u64, chapter.end.ceil() as u64);
+            if self.fits_upload_limit(chapter_file)? {
+                let language = self.language_for_range(start, end);
+                for (task, path) in &chapter_outputs {
+                    self.run_task(*task, chapter_file, path, language).await?;
+                }
+            } else {
+                self.transcribe_large_file(chapter_file, &chapter_outputs, start).await?;
+            }
+            
+            for (j, (task, path)) in chapter_outputs.iter().enumerate() {
+                let transcript = fs::read_to_string(path)?;
+                match self.format() {
+                    OutputFormat::Txt => stitched[j].append_section(&title, &transcript)?,
+                    OutputFormat::Srt | OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => {
+                        let mut cues = self.parse_cues(&transcript)?;
+                        srt::shift(&mut cues, (chapter.start * 1000.0) as u64);
+                        stitched[j].append_cues(cues)?;
+                    }
+                    OutputFormat::Json | OutputFormat::All => {
+                        let mut json = JsonTranscript::parse(&transcript)?;
+                        json.shift(chapter.start);
+                        stitched[j].append_json(json)?;
+                    }
+                }
+                self.finish_output(*task, path, &[], &title, source)?;
+            }
+        }
+        
+        for output in stitched {
+            output.finish()?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Render speaker segments in the configured format
+    /// 
+    /// Text output gets a `Speaker N: ...` paragraph per speaker turn, SRT and
+    /// JSON segments are prefixed with the speaker, and VTT cues use
+    /// `<v Speaker N>` voice spans.
+    fn render_speaker_segments(&self, segments: &[SpeakerSegment]) -> Result<String> {
+        match self.format() {
+            OutputFormat::Txt => {
+                let mut paragraphs: Vec<(&str, Vec<&str>)> = Vec::new();
+                for segment in segments {
+                    match paragraphs.last_mut() {
+                        Some((speaker, texts)) if *speaker == segment.speaker => texts.push(&segment.text),
+                        _ => paragraphs.push((&segment.speaker, vec![&segment.text])),
+                    }
+                }
+                
+                Ok(paragraphs
+                    .iter()
+                    .map(|(speaker, texts)| format!("{}: {}", speaker, texts.join(" ")))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"))
+            }
+            OutputFormat::Srt => {
+                let cues = speaker_cues(segments, |segment| format!("{}: {}", segment.speaker, segment.text));
+                Ok(srt::render_from(&cues, 1))
+            }
+            OutputFormat::Vtt | OutputFormat::Html | OutputFormat::Md => {
+                let cues = speaker_cues(segments, |segment| vtt::voice_span(&segment.speaker, &segment.text));
+                let rendered = vtt::render(&cues);
+                vtt::validate(&rendered)?;
+                Ok(rendered)
+            }
+            OutputFormat::Json | OutputFormat::All => {
+                let segments: Vec<json::Segment> = segments
+                    .iter()
+                    .map(|segment| json::Segment {
+                        start: segment.start_ms as f64 / 1000.0,
+                        end: segment.end_ms as f64 / 1000.0,
+                        text: format!("{}: {}", segment.speaker, segment.text),
+                    })
+                    .collect();
+                let mut transcript = JsonTranscript {
+                    segments,
+                    ..Default::default()
+                };
+                transcript.text = transcript.segments_text();
+                transcript.render()
+            }
+        }
+    }
+    
+    /// Whether a transcript has no text, ignoring timestamps and numbering
+    fn is_empty_transcript(&self, transcript: &str) -> bool {
+        match self.format() {
+            OutputFormat::Txt => transcript.trim().is_empty(),
+            _ => self
+                .parse_cues(transcript)
+                .map_or(true, |cues| cues.iter().all(|cue| cue.text.trim().is_empty())),
+        }
+    }
+    
+    /// Parse a timed transcript in the configured format into cues
+    fn parse_cues(&self, transcript: &str) -> Result<Vec<srt::Cue>> {
+        match self.format() {
+            OutputFormat::Vtt => vtt::parse(transcript),
+            OutputFormat::Json => Ok(JsonTranscript::parse(transcript)?.cues()),
+            _ => srt::parse(transcript),
+        }
+    }
+}
+
+/// A combined transcript written to disk chunk by chunk
+/// 
+/// Only the previous chunk's text and the end of the last cue are kept in
+/// memory, so stitching long recordings doesn't grow with their length. The
+/// output is written to a `.partial` file and renamed once complete, so a
+/// failure never leaves a truncated transcript behind. A `.progress` file next
+/// to it records how far the `.partial` file got, so a later run can resume.
+struct StitchedOutput {
+    format: OutputFormat,
+    path: PathBuf,
+    partial_path: PathBuf,
+    progress_path: PathBuf,
+    writer: BufWriter<File>,
+    /// Number of chunks appended so far
+    chunks_done: usize,
+    /// Whether anything has been written yet
+    started: bool,
+    /// Text of the previous chunk, for trimming words repeated at the seam
+    previous_text: String,
+    /// End of the last written cue, in milliseconds
+    covered_until: u64,
+    /// Number of cues written so far
+    cue_count: usize,
+}
+
+/// Stitching state saved after each chunk, for resuming an interrupted transcript
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    /// Number of chunks the audio was split into
+    chunks: usize,
+    chunks_done: usize,
+    /// Length of the `.partial` file once those chunks were written
+    bytes: u64,
+    started: bool,
+    previous_text: String,
+    covered_until: u64,
+    cue_count: usize,
+}
+
+impl StitchedOutput {
+    /// Start writing a combined transcript to `path`
+    fn create(path: &Path, format: OutputFormat) -> Result<Self> {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        fs::write(output_file, all_transcripts.trim())?;
         
-        info!("Combined transcript saved to: {:?}", output_file);
+        let partial_path = path.with_extension(format!("{}.partial", format.extension()));
+        let progress_path = path.with_extension(format!("{}.progress", format.extension()));
+        if progress_path.exists() {
+            fs::remove_file(&progress_path)?;
+        }
+        
+        let mut writer = BufWriter::new(File::create(&partial_path)?);
+        if format == OutputFormat::Vtt {
+            writer.write_all(vtt::HEADER.as_bytes())?;
+        }
+        
+        Ok(Self {
+            format,
+            path: path.to_path_buf(),
+            partial_path,
+            progress_path,
+            writer,
+            chunks_done: 0,
+            started: false,
+            previous_text: String::new(),
+            covered_until: 0,
+            cue_count: 0,
+        })
+    }
+    
+    /// Continue a transcript of `chunks` chunks that a previous run left unfinished
+    /// 
+    /// Anything written after the last checkpoint is cut off. Starts over when
+    /// there is nothing to resume or the audio was split differently last time.
+    fn resume(path: &Path, format: OutputFormat, chunks: usize) -> Result<Self> {
+        let partial_path = path.with_extension(format!("{}.partial", format.extension()));
+        let progress_path = path.with_extension(format!("{}.progress", format.extension()));
+        
+        let checkpoint = fs::read_to_string(&progress_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Checkpoint>(&content).ok())
+            .filter(|checkpoint| checkpoint.chunks == chunks)
+            .filter(|checkpoint| fs::metadata(&partial_path).is_ok_and(|meta| meta.len() >= checkpoint.bytes));
+        
+        let Some(checkpoint) = checkpoint else {
+            if progress_path.exists() {
+                warn!("Can't resume {:?} from its previous progress, starting over", path);
+            }
+            return Self::create(path, format);
+        };
+        
+        info!("Resuming {:?} after chunk {}/{}", path, checkpoint.chunks_done, chunks);
+        let mut file = OpenOptions::new().write(true).open(&partial_path)?;
+        file.set_len(checkpoint.bytes)?;
+        file.seek(SeekFrom::End(0))?;
+        
+        Ok(Self {
+            format,
+            path: path.to_path_buf(),
+            partial_path,
+            progress_path,
+            writer: BufWriter::new(file),
+            chunks_done: checkpoint.chunks_done,
+            started: checkpoint.started,
+            previous_text: checkpoint.previous_text,
+            covered_until: checkpoint.covered_until,
+            cue_count: checkpoint.cue_count,
+        })
+    }
+    
+    /// Record that another chunk has been appended, out of `chunks` in total
+    fn checkpoint(&mut self, chunks: usize) -> Result<()> {
+        self.writer.flush()?;
+        self.chunks_done += 1;
+        
+        let checkpoint = Checkpoint {
+            chunks,
+            chunks_done: self.chunks_done,
+            bytes: self.writer.get_ref().metadata()?.len(),
+            started: self.started,
+            previous_text: self.previous_text.clone(),
+            covered_until: self.covered_until,
+            cue_count: self.cue_count,
+        };
+        fs::write(&self.progress_path, serde_json::to_string(&checkpoint)?)?;
+        Ok(())
+    }
+    
+    /// Append a plain text chunk, skipping words repeated from the end of the previous chunk
+    fn append_text(&mut self, text: &str) -> Result<()> {
+        let skip = seam_word_count(&self.previous_text, text);
+        let text = if skip == 0 {
+            text.trim().to_string()
+        } else {
+            debug!("Dropping {} words repeated across a chunk seam", skip);
+            text.split_whitespace().skip(skip).collect::<Vec<_>>().join(" ")
+        };
+        
+        self.write_paragraph(&text)?;
+        self.previous_text = text;
+        Ok(())
+    }
+    
+    /// Append a JSON chunk as one line, dropping what the previous chunk's overlap already covered
+    /// 
+    /// The lines are merged into a single transcript by [`Self::finish`].
+    fn append_json(&mut self, mut json: JsonTranscript) -> Result<()> {
+        json.drop_before(self.covered_until as f64 / 1000.0);
+        writeln!(self.writer, "{}", json.render_line()?)?;
+        
+        if let Some(end) = json.end() {
+            self.covered_until = (end * 1000.0).round() as u64;
+        }
+        Ok(())
+    }
+    
+    /// Append a plain text section under a `## Title` heading
+    fn append_section(&mut self, title: &str, text: &str) -> Result<()> {
+        self.write_paragraph(&format!("## {}\n\n{}", title, text.trim()))
+    }
+    
+    /// Append timed cues, dropping those already covered by the previous chunk's overlap
+    fn append_cues(&mut self, mut cues: Vec<srt::Cue>) -> Result<()> {
+        let covered_until = self.covered_until;
+        cues.retain(|cue| (cue.start_ms + cue.end_ms) / 2 >= covered_until);
+        
+        let rendered = match self.format {
+            OutputFormat::Vtt => vtt::render_cues(&cues),
+            _ => srt::render_from(&cues, self.cue_count + 1),
+        };
+        self.writer.write_all(rendered.as_bytes())?;
+        
+        if let Some(last) = cues.last() {
+            self.covered_until = last.end_ms;
+        }
+        self.cue_count += cues.len();
+        Ok(())
+    }
+    
+    /// Write a paragraph, separated from the previous one by a blank line
+    fn write_paragraph(&mut self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if self.started {
+            self.writer.write_all(b"\n\n")?;
+        }
+        self.writer.write_all(text.as_bytes())?;
+        self.started = true;
+        Ok(())
+    }
+    
+    /// Flush the transcript and move it into place
+    /// 
+    /// JSON chunks, written one per line, are merged into one transcript first.
+    fn finish(mut self) -> Result<()> {
+        self.writer.flush()?;
+        drop(self.writer);
+        if self.format == OutputFormat::Json {
+            let mut merged = JsonTranscript::default();
+            for line in fs::read_to_string(&self.partial_path)?.lines().filter(|line| !line.trim().is_empty()) {
+                merged.append(JsonTranscript::parse(line)?);
+            }
+            fs::write(&self.partial_path, merged.render()?)?;
+        }
+        fs::rename(&self.partial_path, &self.path)?;
+        if self.progress_path.exists() {
+            fs::remove_file(&self.progress_path)?;
+        }
+        
+        info!("Combined transcript saved to: {:?}", self.path);
         Ok(())
     }
 }
+
+/// Number of leading words in `next` that repeat the tail of `previous`
+/// 
+/// Words are compared ignoring case and punctuation. The first couple of words
+/// in `next` may be a fragment cut mid-word by the overlap, so those can be
+/// skipped before the repeated run starts.
+fn seam_word_count(previous: &str, next: &str) -> usize {
+    let normalize = |word: &str| {
+        word.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    
+    let previous: Vec<String> = previous.split_whitespace().map(normalize).collect();
+    let next: Vec<String> = next.split_whitespace().map(normalize).collect();
+    let tail = &previous[previous.len().saturating_sub(MAX_SEAM_WORDS)..];
+    
+    for partial in 0..=MAX_SEAM_PARTIAL_WORDS.min(next.len()) {
+        let candidates = &next[partial..];
+        let longest = tail.len().min(candidates.len());
+        for n in (MIN_SEAM_WORDS..=longest).rev() {
+            if tail[tail.len() - n..] == candidates[..n] {
+                return partial + n;
+            }
+        }
+    }
+    
+    0
+}
+
+/// Caption cues for speaker segments, with the cue text built by `text`
+fn speaker_cues(segments: &[SpeakerSegment], text: impl Fn(&SpeakerSegment) -> String) -> Vec<srt::Cue> {
+    segments
+        .iter()
+        .map(|segment| srt::Cue {
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            text: text(segment),
+        })
+        .collect()
+}
+
+/// Path of a chapter's transcript, e.g. `chapters/03_Title/transcript.txt` next to `path`
+fn chapter_path(path: &Path, number: usize, title: &str) -> PathBuf {
+    let dir_name = format!("{:02}_{}", number, utils::sanitize_filename(title));
+    let file_name = path.file_name().unwrap_or_default();
+    path.with_file_name("chapters").join(dir_name).join(file_name)
+}
+
+/// Path the original-language transcript is written to for a given output file, tagged with
+/// `--language` (or `default_language`) when `--translate-also` writes a translation next to it
+pub fn transcript_path(config: &Config, default_language: Option<&str>, output_file: &Path) -> PathBuf {
+    if config.translate_also {
+        let language = config.language.as_deref().or(default_language).unwrap_or("original");
+        language_tagged_path(output_file, language)
+    } else {
+        output_file.to_path_buf()
+    }
+}
+
+/// Insert a language tag before the extension, e.g. `transcript.txt` -> `transcript.en.txt`
+fn language_tagged_path(path: &Path, language: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("transcript");
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => path.with_file_name(format!("{}.{}.{}", stem, language, ext)),
+        None => path.with_file_name(format!("{}.{}", stem, language)),
+    }
+}
+
+/// Write a transcript with each writer, next to `path` with the writer's extension
+fn write_outputs(writers: &[Box<dyn OutputWriter>], transcript: &JsonTranscript, meta: &TranscriptMeta, path: &Path) -> Result<()> {
+    for writer in writers {
+        writer.write(transcript, meta, &path.with_extension(writer.extension()))?;
+    }
+    Ok(())
+}
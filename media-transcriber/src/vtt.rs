@@ -0,0 +1,94 @@
+use anyhow::Result;
+use regex::Regex;
+
+use crate::srt::{self, Cue};
+
+/// Parse WebVTT content into cues
+///
+/// The header, `NOTE`/`STYLE` blocks, and cue settings are dropped; cue text,
+/// including any voice spans, is kept as-is.
+pub fn parse(content: &str) -> Result<Vec<Cue>> {
+    let without_settings: Vec<String> = content
+        .lines()
+        .map(|line| match line.split_once("-->") {
+            // Cue settings (`align:start` etc.) follow the end timestamp
+            Some((start, end)) => format!(
+                "{} --> {}",
+                start.trim(),
+                end.split_whitespace().next().unwrap_or_default()
+            ),
+            None => line.to_string(),
+        })
+        .collect();
+    
+    srt::parse(&without_settings.join("\n"))
+}
+
+/// Header every WebVTT file starts with
+pub const HEADER: &str = "WEBVTT\n\n";
+
+/// Render cues as WebVTT content
+pub fn render(cues: &[Cue]) -> String {
+    format!("{}{}", HEADER, render_cues(cues))
+}
+
+/// Render cues without the WebVTT header, for appending to an existing file
+pub fn render_cues(cues: &[Cue]) -> String {
+    let mut output = String::new();
+    
+    for cue in cues {
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start_ms),
+            format_timestamp(cue.end_ms),
+            cue.text
+        ));
+    }
+    
+    output
+}
+
+/// Cue text attributed to a speaker with a `<v>` voice span
+///
+/// Both the speaker name and the text are escaped so neither can break out of
+/// the span or form a `-->` sequence.
+pub fn voice_span(speaker: &str, text: &str) -> String {
+    format!("<v {}>{}", escape(speaker.trim()), escape(text.trim()))
+}
+
+/// Check rendered WebVTT for a valid header, timing lines, and voice tag syntax
+pub fn validate(content: &str) -> Result<()> {
+    if !content.starts_with("WEBVTT") {
+        return Err(anyhow::anyhow!("WebVTT output must start with a WEBVTT header"));
+    }
+    
+    // `<v>` or `<v.class>` followed by a space and an annotation without `<`, `>` or `&` (unless escaped)
+    let voice_tag = Regex::new(r"^<v(\.[^\s.&<>]+)* ([^\n&<>]|&(amp|lt|gt);)+>").unwrap();
+    
+    for line in content.lines() {
+        if line.contains("-->") {
+            parse(line)?;
+            continue;
+        }
+        
+        for (i, _) in line.match_indices("<v") {
+            if !voice_tag.is_match(&line[i..]) {
+                return Err(anyhow::anyhow!("Invalid WebVTT voice tag in: {}", line));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Escape characters that are special in WebVTT cue text
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Format milliseconds as a WebVTT timestamp (`HH:MM:SS.mmm`)
+fn format_timestamp(ms: u64) -> String {
+    srt::format_timestamp(ms).replace(',', ".")
+}
@@ -0,0 +1,239 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use crate::config::OutputFormat;
+use crate::json::JsonTranscript;
+use crate::srt::{self, Cue};
+use crate::utils::Chapter;
+use crate::{html, markdown, vtt};
+
+/// Longest a chapter title cue stays on screen in SRT/VTT output, in milliseconds
+const CHAPTER_CUE_MS: u64 = 3000;
+
+/// What writers know about a transcript besides its content
+pub struct TranscriptMeta<'a> {
+    /// Episode or video title, for formats with a heading
+    pub title: &'a str,
+    /// Feed item URL, video URL, or local path the audio came from
+    pub source: &'a str,
+    /// Chapters to mark, as cues or headings depending on the format
+    pub chapters: &'a [Chapter],
+}
+
+/// Writes finished transcripts in one output format
+/// 
+/// Every transcript reaches the writers as a [`JsonTranscript`], whichever
+/// format it was transcribed in, so adding a format means adding a writer
+/// and returning it from [`writers_for`].
+pub trait OutputWriter {
+    /// Extension of the files this writer produces
+    fn extension(&self) -> &'static str;
+    
+    /// Render the transcript as this writer's format
+    fn render(&self, transcript: &JsonTranscript, meta: &TranscriptMeta) -> Result<String>;
+    
+    /// Render the transcript and write it to `path`
+    fn write(&self, transcript: &JsonTranscript, meta: &TranscriptMeta, path: &Path) -> Result<()> {
+        fs::write(path, self.render(transcript, meta)?)?;
+        Ok(())
+    }
+}
+
+/// Plain text, with chapter headings only when chapters were transcribed separately
+pub struct TxtWriter;
+
+impl OutputWriter for TxtWriter {
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, _meta: &TranscriptMeta) -> Result<String> {
+        Ok(transcript.text.clone())
+    }
+}
+
+/// SubRip subtitles, with a cue at the start of each chapter
+pub struct SrtWriter;
+
+impl OutputWriter for SrtWriter {
+    fn extension(&self) -> &'static str {
+        "srt"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, meta: &TranscriptMeta) -> Result<String> {
+        Ok(srt::render_from(&cues_with_chapters(transcript, meta.chapters), 1))
+    }
+}
+
+/// WebVTT captions, with a cue at the start of each chapter
+pub struct VttWriter;
+
+impl OutputWriter for VttWriter {
+    fn extension(&self) -> &'static str {
+        "vtt"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, meta: &TranscriptMeta) -> Result<String> {
+        Ok(vtt::render(&cues_with_chapters(transcript, meta.chapters)))
+    }
+}
+
+/// The structured transcript itself
+pub struct JsonWriter;
+
+impl OutputWriter for JsonWriter {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, _meta: &TranscriptMeta) -> Result<String> {
+        transcript.render()
+    }
+}
+
+/// Markdown with a timestamped paragraph per segment and a heading per chapter
+pub struct MarkdownWriter;
+
+impl OutputWriter for MarkdownWriter {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, meta: &TranscriptMeta) -> Result<String> {
+        Ok(markdown::render(meta.title, meta.source, &transcript.cues(), meta.chapters))
+    }
+}
+
+/// A self-contained web page, with a segment at the start of each chapter
+pub struct HtmlWriter;
+
+impl OutputWriter for HtmlWriter {
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+    
+    fn render(&self, transcript: &JsonTranscript, meta: &TranscriptMeta) -> Result<String> {
+        Ok(html::render(meta.title, meta.source, &cues_with_chapters(transcript, meta.chapters)))
+    }
+}
+
+/// Writers for `--format`; `all` writes the JSON plus text and SRT made from it
+pub fn writers_for(format: OutputFormat) -> Vec<Box<dyn OutputWriter>> {
+    match format {
+        OutputFormat::Txt => vec![Box::new(TxtWriter)],
+        OutputFormat::Srt => vec![Box::new(SrtWriter)],
+        OutputFormat::Vtt => vec![Box::new(VttWriter)],
+        OutputFormat::Html => vec![Box::new(HtmlWriter)],
+        OutputFormat::Md => vec![Box::new(MarkdownWriter)],
+        OutputFormat::Json => vec![Box::new(JsonWriter)],
+        OutputFormat::All => vec![Box::new(JsonWriter), Box::new(TxtWriter), Box::new(SrtWriter)],
+    }
+}
+
+/// The transcript's segments as cues, with a `Chapter: <title>` cue at the start of each chapter
+fn cues_with_chapters(transcript: &JsonTranscript, chapters: &[Chapter]) -> Vec<Cue> {
+    let mut cues = transcript.cues();
+    for (i, chapter) in chapters.iter().enumerate() {
+        let start_ms = (chapter.start * 1000.0) as u64;
+        let end_ms = ((chapter.end * 1000.0) as u64).min(start_ms + CHAPTER_CUE_MS);
+        let title = chapter.title.clone().unwrap_or_else(|| format!("Chapter {}", i + 1));
+        
+        // Ahead of any speech starting at the same moment
+        let position = cues.partition_point(|cue| cue.start_ms < start_ms);
+        cues.insert(
+            position,
+            Cue {
+                start_ms,
+                end_ms,
+                text: format!("Chapter: {}", title),
+            },
+        );
+    }
+    cues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::Segment;
+    
+    fn sample_transcript() -> JsonTranscript {
+        JsonTranscript {
+            text: "Welcome to the show. Fish & chips <cheap> tonight.".to_string(),
+            language: Some("en".to_string()),
+            segments: vec![
+                Segment { start: 0.0, end: 2.5, text: " Welcome to the show.".to_string() },
+                Segment { start: 3661.25, end: 3663.0, text: " Fish & chips <cheap> tonight.".to_string() },
+            ],
+            words: Vec::new(),
+        }
+    }
+    
+    fn render(writer: &dyn OutputWriter) -> String {
+        let chapters = [Chapter { start: 60.0, end: 120.0, title: Some("Intro".to_string()) }];
+        let meta = TranscriptMeta {
+            title: "Episode 1",
+            source: "https://example.com/1.mp3",
+            chapters: &chapters,
+        };
+        writer.render(&sample_transcript(), &meta).unwrap()
+    }
+    
+    #[test]
+    fn txt_is_the_transcript_text() {
+        assert_eq!(render(&TxtWriter), sample_transcript().text);
+    }
+    
+    #[test]
+    fn srt_cues_are_numbered_from_one_with_comma_milliseconds() {
+        let srt = render(&SrtWriter);
+        let blocks: Vec<&str> = srt.trim().split("\n\n").collect();
+        
+        assert_eq!(blocks.len(), 3);
+        for (i, block) in blocks.iter().enumerate() {
+            assert!(block.starts_with(&format!("{}\n", i + 1)), "{}", block);
+        }
+        assert!(srt.contains("00:00:00,000 --> 00:00:02,500\nWelcome to the show."));
+        assert!(srt.contains("00:01:00,000 --> 00:01:03,000\nChapter: Intro"));
+        assert!(srt.contains("01:01:01,250 --> 01:01:03,000\nFish & chips <cheap> tonight."));
+    }
+    
+    #[test]
+    fn vtt_starts_with_the_header_and_uses_dot_milliseconds() {
+        let vtt = render(&VttWriter);
+        
+        assert!(vtt.starts_with("WEBVTT"));
+        assert!(vtt.contains("00:00:00.000 --> 00:00:02.500"));
+        assert!(vtt.contains("01:01:01.250 --> 01:01:03.000"));
+        assert!(!vtt.contains(','), "{}", vtt);
+    }
+    
+    #[test]
+    fn json_parses_back() {
+        let json = render(&JsonWriter);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        
+        assert_eq!(value["text"], sample_transcript().text.as_str());
+        assert_eq!(value["segments"].as_array().unwrap().len(), 2);
+        assert_eq!(JsonTranscript::parse(&json).unwrap().segments[1].start, 3661.25);
+    }
+    
+    #[test]
+    fn markdown_has_the_title_chapters_and_segments() {
+        let markdown = render(&MarkdownWriter);
+        
+        assert!(markdown.contains("Episode 1"));
+        assert!(markdown.contains("Intro"));
+        assert!(markdown.contains("Welcome to the show."));
+    }
+    
+    #[test]
+    fn html_escapes_transcript_text() {
+        let html = render(&HtmlWriter);
+        
+        assert!(html.contains("Fish &amp; chips &lt;cheap&gt; tonight."), "{}", html);
+        assert!(!html.contains("<cheap>"));
+        assert!(!html.contains("Fish & chips"));
+    }
+}
@@ -1,16 +1,28 @@
 use anyhow::{Context, Result};
-use log::{debug, error, info};
+use chrono::NaiveDate;
+use log::{debug, error, info, warn};
 use regex::Regex;
 use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::tempdir;
+use thiserror::Error;
 
-use crate::config::Config;
-use crate::transcription::TranscriptionService;
+use crate::combined::CombinedTranscript;
+use crate::config::{Config, Order};
+use crate::embed;
+use crate::filter::ItemMeta;
+use crate::listing::{self, ListedItem};
+use crate::manifest::{Manifest, SourceKind};
+use crate::srt;
+use crate::template::{ItemNaming, ItemPaths};
+use crate::transcription::{self, TranscriptionService};
 use crate::utils;
 
+/// yt-dlp format selector for `--low-bandwidth`: the best audio stream up to 64 kbps, else the smallest one
+const LOW_BANDWIDTH_FORMAT: &str = "bestaudio[abr<=64]/worstaudio/worst";
+
 /// YouTube processor for downloading and transcribing videos
 pub struct YouTubeProcessor<'a> {
     config: &'a Config,
@@ -25,6 +37,101 @@ struct VideoInfo {
     channel: Option<String>,
     description: Option<String>,
     duration: Option<f64>,
+    /// Chapters from the video description, if the uploader added any
+    chapters: Option<Vec<VideoChapter>>,
+}
+
+/// A chapter of a video, in seconds
+#[derive(Debug, Deserialize)]
+struct VideoChapter {
+    start_time: f64,
+    end_time: f64,
+    title: Option<String>,
+}
+
+impl VideoInfo {
+    /// Parse yt-dlp's `YYYYMMDD` upload date
+    fn parsed_upload_date(&self) -> Option<NaiveDate> {
+        self.upload_date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+    }
+    
+    /// The video's chapters, empty if it has none
+    fn chapters(&self) -> Vec<utils::Chapter> {
+        self.chapters
+            .iter()
+            .flatten()
+            .map(|chapter| utils::Chapter {
+                start: chapter.start_time,
+                end: chapter.end_time,
+                title: chapter.title.clone(),
+            })
+            .collect()
+    }
+    
+    /// Channel name, or `Unknown_Channel` when yt-dlp doesn't report one
+    fn channel_name(&self) -> &str {
+        self.channel.as_deref().unwrap_or("Unknown_Channel")
+    }
+}
+
+/// Known reasons a video can't be fetched, which skip it rather than count as failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unavailability {
+    Private,
+    Deleted,
+    MembersOnly,
+    GeoBlocked,
+}
+
+impl Unavailability {
+    /// Recognise the reason in yt-dlp's error output, if it's one of the known ones
+    fn from_stderr(stderr: &str) -> Option<Self> {
+        let stderr = stderr.to_lowercase();
+        if stderr.contains("in your country") || stderr.contains("geo restrict") || stderr.contains("geo-restrict") {
+            Some(Self::GeoBlocked)
+        } else if stderr.contains("members-only") || stderr.contains("join this channel") {
+            Some(Self::MembersOnly)
+        } else if stderr.contains("private video") {
+            Some(Self::Private)
+        } else if stderr.contains("has been removed")
+            || stderr.contains("no longer available")
+            || stderr.contains("has been terminated")
+            || stderr.contains("video unavailable")
+        {
+            Some(Self::Deleted)
+        } else {
+            None
+        }
+    }
+    
+    /// Name of the reason, as shown in logs and the run summary
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Private => "private",
+            Self::Deleted => "deleted",
+            Self::MembersOnly => "members-only",
+            Self::GeoBlocked => "geo-blocked",
+        }
+    }
+    
+    /// What might still get the video, if anything
+    fn hint(&self) -> &'static str {
+        match self {
+            Self::Private | Self::Deleted => "",
+            Self::MembersOnly => "; pass --cookies or --cookies-from-browser from a member's account",
+            Self::GeoBlocked => "; try --geo-bypass, or --proxy with a server in an allowed country",
+        }
+    }
+}
+
+/// yt-dlp couldn't get a video for a known reason, so it should be skipped
+#[derive(Debug, Error)]
+#[error("{url} is {}{}", reason.name(), reason.hint())]
+pub struct VideoUnavailable {
+    pub url: String,
+    pub reason: Unavailability,
 }
 
 impl<'a> YouTubeProcessor<'a> {
@@ -38,10 +145,11 @@ impl<'a> YouTubeProcessor<'a> {
         info!("Processing YouTube URL: {}", url);
         
         // Check if yt-dlp is installed
-        if !utils::check_command("yt-dlp") {
-            return Err(anyhow::anyhow!(
-                "yt-dlp is not installed. Please install it with 'brew install yt-dlp' or visit https://github.com/yt-dlp/yt-dlp"
-            ));
+        ensure_yt_dlp()?;
+        
+        // Explicit video IDs take precedence over processing the whole source
+        if !self.config.video_ids.is_empty() {
+            return self.process_video_ids(Some(url)).await;
         }
         
         // Determine if this is a single video or a channel/playlist
@@ -54,6 +162,99 @@ impl<'a> YouTubeProcessor<'a> {
         Ok(())
     }
     
+    /// Process only the video IDs listed in the config
+    /// 
+    /// When a channel or playlist URL is given, IDs that are not part of it are
+    /// reported but still processed, since the user asked for them explicitly.
+    pub async fn process_video_ids(&self, source_url: Option<&str>) -> Result<()> {
+        ensure_yt_dlp()?;
+        
+        let id_pattern = Regex::new(r"^[\w-]{11}$").unwrap();
+        let (valid_ids, invalid_ids): (Vec<&String>, Vec<&String>) = self
+            .config
+            .video_ids
+            .iter()
+            .partition(|id| id_pattern.is_match(id));
+        
+        for id in &invalid_ids {
+            warn!("Ignoring invalid YouTube video ID: {}", id);
+        }
+        
+        if valid_ids.is_empty() {
+            return Err(anyhow::anyhow!("No valid YouTube video IDs given"));
+        }
+        
+        // Warn about IDs that are not part of the given channel/playlist
+        if let Some(url) = source_url.filter(|url| !self.is_single_video(url)) {
+            let source_ids = self.get_video_ids(url)?;
+            for id in &valid_ids {
+                if !source_ids.contains(id) {
+                    warn!("Video ID {} was not found in {}", id, url);
+                }
+            }
+        }
+        
+        info!("Processing {} selected videos", valid_ids.len());
+        
+        for (i, id) in valid_ids.iter().enumerate() {
+            let video_url = format!("https://www.youtube.com/watch?v={}", id);
+            info!("Processing video {}/{}: {}", i + 1, valid_ids.len(), video_url);
+            
+            if let Err(e) = self.process_single_video(&video_url).await {
+                error!("Failed to process video {}: {}", id, e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// List the videos of a URL that would be processed, without downloading anything
+    /// 
+    /// Channels and playlists are listed from yt-dlp's flat listing, which usually
+    /// lacks upload dates, so date filters only apply where a date is known.
+    pub async fn list(&self, url: &str) -> Result<Vec<ListedItem>> {
+        ensure_yt_dlp()?;
+        
+        if self.is_single_video(url) {
+            let video_info = self.get_video_info(url)?;
+            return Ok(vec![ListedItem {
+                source: video_info.channel.clone().unwrap_or_else(|| url.to_string()),
+                date: video_info.parsed_upload_date(),
+                duration: video_info.duration,
+                title: video_info.title,
+                url: url.to_string(),
+            }]);
+        }
+        
+        let source = self
+            .get_channel_info(url)?
+            .channel
+            .unwrap_or_else(|| url.to_string());
+        let mut entries = self.get_playlist_entries(url, None)?;
+        if self.config.shuffle_items {
+            self.config.shuffle(&mut entries, url);
+        }
+        
+        let mut items = entries
+            .iter()
+            .map(|entry| entry.listed(&source))
+            .filter(|item| {
+                self.config.accepts(&ItemMeta {
+                    title: &item.title,
+                    duration: item.duration,
+                    date: item.date,
+                }) && self.config.duration_out_of_bounds(item.duration).is_none()
+            })
+            .take(self.config.limit.unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+        
+        if let Some(count) = self.config.items_within_max_minutes(items.iter().map(|item| item.duration)) {
+            items.truncate(count);
+        }
+        
+        Ok(items)
+    }
+    
     /// Check if URL is a single video
     fn is_single_video(&self, url: &str) -> bool {
         // YouTube video URL patterns
@@ -80,16 +281,77 @@ impl<'a> YouTubeProcessor<'a> {
         // Get video info
         let video_info = self.get_video_info(url)?;
         
+        // Overlapping sources (or an earlier run) may already have covered this video
+        if !self.config.seen_videos.insert(&video_info.id) {
+            info!("Skipping already processed video: {}", video_info.title);
+            return Ok(());
+        }
+        
+        // Estimate cost and confirm before downloading
+        if !utils::confirm_estimated_cost(self.config, &[video_info.duration])? {
+            return Ok(());
+        }
+        
         // Create video directory
-        let video_dir = self.create_video_directory(&video_info)?;
+        let channel_dir = self.config.source_dir(video_info.channel_name());
+        let Some(paths) = self.create_video_directory(&video_info, &channel_dir)? else {
+            return Ok(());
+        };
         
         // Save video info
-        self.save_video_info(&video_info, url, &video_dir)?;
+        self.save_video_info(&video_info, url, &paths.info)?;
+        
+        let mut manifest = Manifest::new(self.config, SourceKind::Youtube, &video_info.title, url, None);
+        
+        if self.config.dry_run {
+            self.log_planned_video(&video_info);
+            manifest.skipped(&video_info.title, url, video_info.duration, "dry run");
+            return manifest.write(&paths.dir);
+        }
+        
+        if self.config.detect_language_only {
+            let result = self.detect_video_language(&video_info, url, &paths.info).await;
+            match &result {
+                Ok(()) => manifest.skipped(&video_info.title, url, video_info.duration, "language detection only"),
+                Err(e) => manifest.failed(&video_info.title, url, video_info.duration, e),
+            }
+            manifest.write(&paths.dir)?;
+            return result;
+        }
         
         // Download and transcribe video
-        self.download_and_transcribe_video(url, &video_dir).await?;
+        let result = utils::ItemDeadline::start(self.config)
+            .run(self.download_and_transcribe_video(&video_info, url, &paths))
+            .await;
+        match &result {
+            Ok(()) => {
+                manifest.success(&video_info.title, url, &transcription::transcript_path(self.config, None, &paths.transcript), video_info.duration);
+                self.config.seen_videos.persist(&video_info.id);
+                paths.clear_failure();
+            }
+            Err(e) if e.is::<utils::TooLarge>() => {
+                warn!("Skipping video {}: {}", video_info.title, e);
+                manifest.skipped(&video_info.title, url, video_info.duration, "larger than --max-filesize");
+                return manifest.write(&paths.dir);
+            }
+            Err(e) => {
+                manifest.failed(&video_info.title, url, video_info.duration, e);
+                paths.record_failure(e);
+            }
+        }
+        manifest.write(&paths.dir)?;
         
-        Ok(())
+        result
+    }
+    
+    /// Log what would be transcribed for a video in dry-run mode
+    fn log_planned_video(&self, video_info: &VideoInfo) {
+        info!(
+            "[dry run] Would transcribe: {} (uploaded: {}, duration: {})",
+            video_info.title,
+            video_info.upload_date.as_deref().unwrap_or("unknown"),
+            utils::format_duration(video_info.duration)
+        );
     }
     
     /// Process a YouTube channel or playlist
@@ -105,46 +367,228 @@ impl<'a> YouTubeProcessor<'a> {
         // Save channel info
         self.save_channel_info(&channel_info, url, &channel_dir)?;
         
-        // Get video URLs
-        let video_urls = self.get_video_urls(url)?;
+        // Get videos
+        let mut entries = self.get_playlist_entries(url, Some(&channel_dir))?;
+        if entries.is_empty() {
+            return Err(utils::NothingToTranscribe("channel/playlist returned no videos".to_string()).into());
+        }
+        if self.config.shuffle_items {
+            self.config.shuffle(&mut entries, url);
+        }
         
-        // Apply limit if specified
-        let videos_to_process = if let Some(limit) = self.config.limit {
-            if video_urls.len() > limit {
-                info!("Limiting to {} videos (out of {})", limit, video_urls.len());
-                video_urls[0..limit].to_vec()
+        // Apply limit if specified. With filters, titles and dates are only known once
+        // each video's info is fetched, so the limit counts matching videos instead.
+        let filtering = self.config.has_item_filters() || self.config.has_duration_bounds();
+        let mut videos_to_process = if filtering {
+            entries
+        } else if let Some(limit) = self.config.limit {
+            if entries.len() > limit {
+                info!("Limiting to {} videos (out of {})", limit, entries.len());
+                entries[0..limit].to_vec()
             } else {
-                video_urls
+                entries
             }
         } else {
-            video_urls
+            entries
         };
         
+        // Likewise for the duration cap
+        if !filtering {
+            let durations = videos_to_process.iter().map(|entry| entry.duration);
+            if let Some(count) = self.config.items_within_max_minutes(durations) {
+                if count < videos_to_process.len() {
+                    info!(
+                        "Limiting to {} videos (out of {}) to stay within --max-minutes",
+                        count,
+                        videos_to_process.len()
+                    );
+                    videos_to_process.truncate(count);
+                }
+            }
+        }
+        
+        // Let the user hand-pick from what's left
+        let mut unselected = Vec::new();
+        if self.config.interactive {
+            let source = channel_info.channel.as_deref().unwrap_or(url);
+            let (picked, rest) = listing::pick_interactively(videos_to_process, |entry| entry.listed(source))?;
+            videos_to_process = picked;
+            unselected = rest;
+        }
+        
+        // Estimate cost and confirm before doing any heavy work
+        let durations: Vec<Option<f64>> = videos_to_process.iter().map(|entry| entry.duration).collect();
+        if !utils::confirm_estimated_cost(self.config, &durations)? {
+            return Ok(());
+        }
+        
+        let channel_name = channel_info.channel.as_deref().unwrap_or("Unknown Channel");
+        let mut manifest = Manifest::new(self.config, SourceKind::Youtube, channel_name, url, None);
+        for entry in &unselected {
+            manifest.skipped(entry.title.as_deref().unwrap_or(&entry.url), &entry.url, entry.duration, "not selected");
+        }
+        
+        // Combined playlist subtitles, if requested
+        let mut combined = self.config.combined_srt.then(CombinedSrt::default);
+        let mut combined_transcript = self
+            .config
+            .combined
+            .then(|| CombinedTranscript::new(channel_name, self.config.format));
+        
+        let mut matched_count = 0;
+        let mut matched_secs = 0.0;
+        let mut filtered_count = 0;
+        
         // Process each video
-        for (i, video_url) in videos_to_process.iter().enumerate() {
+        for (i, entry) in videos_to_process.iter().enumerate() {
+            let video_url = &entry.url;
+            if self.config.limit.is_some_and(|limit| matched_count >= limit) {
+                info!("Reached limit of {} matching videos", matched_count);
+                break;
+            }
+            
             info!("Processing video {}/{}: {}", i + 1, videos_to_process.len(), video_url);
             
+            // Overlapping sources (or an earlier run) may already have covered this video
+            if self.config.seen_videos.contains(&entry.id) {
+                let title = entry.title.as_deref().unwrap_or(video_url);
+                info!("Skipping already processed video: {}", title);
+                manifest.skipped(title, video_url, entry.duration, "already processed");
+                continue;
+            }
+            
+            // The listing's duration, when it has one, spares fetching the info of e.g. Shorts
+            if let (Some(_), Some(reason)) = (entry.duration, self.config.duration_out_of_bounds(entry.duration)) {
+                let title = entry.title.as_deref().unwrap_or(video_url);
+                debug!("Skipping video {}: {}", title, reason);
+                manifest.skipped(title, video_url, entry.duration, reason);
+                filtered_count += 1;
+                continue;
+            }
+            
             // Get video info
             match self.get_video_info(video_url) {
                 Ok(video_info) => {
+                    // Apply title filter and filter file rules
+                    let meta = ItemMeta {
+                        title: &video_info.title,
+                        duration: video_info.duration.or(entry.duration),
+                        date: video_info.parsed_upload_date(),
+                    };
+                    if !self.config.accepts(&meta) {
+                        debug!("Skipping filtered video: {}", video_info.title);
+                        manifest.skipped(&video_info.title, video_url, video_info.duration, "filtered");
+                        filtered_count += 1;
+                        continue;
+                    }
+                    if let Some(reason) = self.config.duration_out_of_bounds(meta.duration) {
+                        debug!("Skipping video {}: {}", video_info.title, reason);
+                        manifest.skipped(&video_info.title, video_url, meta.duration, reason);
+                        filtered_count += 1;
+                        continue;
+                    }
+                    
+                    // Videos of unknown duration can't count towards --max-minutes
+                    let secs = meta.duration.unwrap_or_default();
+                    let over_budget = self
+                        .config
+                        .max_minutes
+                        .is_some_and(|max_minutes| matched_secs + secs > max_minutes as f64 * 60.0);
+                    if filtering && over_budget {
+                        info!("Reached --max-minutes after {} matching videos", matched_count);
+                        break;
+                    }
+                    matched_count += 1;
+                    matched_secs += secs;
+                    self.config.seen_videos.insert(&video_info.id);
+                    
                     // Create video directory
-                    let video_dir = channel_dir.join(utils::sanitize_filename(&video_info.title));
-                    fs::create_dir_all(&video_dir)?;
+                    let Some(paths) = self.create_video_directory(&video_info, &channel_dir)? else {
+                        manifest.skipped(&video_info.title, video_url, video_info.duration, "name taken by another video");
+                        continue;
+                    };
                     
                     // Save video info
-                    self.save_video_info(&video_info, video_url, &video_dir)?;
+                    self.save_video_info(&video_info, video_url, &paths.info)?;
+                    
+                    if self.config.dry_run {
+                        self.log_planned_video(&video_info);
+                        manifest.skipped(&video_info.title, video_url, video_info.duration, "dry run");
+                        continue;
+                    }
+                    
+                    if self.config.detect_language_only {
+                        match self.detect_video_language(&video_info, video_url, &paths.info).await {
+                            Ok(()) => manifest.skipped(&video_info.title, video_url, video_info.duration, "language detection only"),
+                            Err(e) => {
+                                error!("Failed to detect video language: {}", e);
+                                manifest.failed(&video_info.title, video_url, video_info.duration, &e);
+                            }
+                        }
+                        continue;
+                    }
                     
                     // Download and transcribe video
-                    if let Err(e) = self.download_and_transcribe_video(video_url, &video_dir).await {
+                    let deadline = utils::ItemDeadline::start(self.config);
+                    if let Err(e) = deadline.run(self.download_and_transcribe_video(&video_info, video_url, &paths)).await {
+                        if e.is::<utils::TooLarge>() {
+                            warn!("Skipping video {}: {}", video_info.title, e);
+                            manifest.skipped(&video_info.title, video_url, video_info.duration, "larger than --max-filesize");
+                            continue;
+                        }
                         error!("Failed to process video: {}", e);
+                        manifest.failed(&video_info.title, video_url, video_info.duration, &e);
+                        paths.record_failure(&e);
+                        continue;
+                    }
+                    
+                    let transcript_file = transcription::transcript_path(self.config, None, &paths.transcript);
+                    manifest.success(&video_info.title, video_url, &transcript_file, video_info.duration);
+                    self.config.seen_videos.persist(&video_info.id);
+                    paths.clear_failure();
+                    
+                    if let Some(combined_transcript) = combined_transcript.as_mut() {
+                        combined_transcript.add(&video_info.title, video_info.parsed_upload_date(), &transcript_file);
+                    }
+                    
+                    if let Some(combined) = combined.as_mut() {
+                        if let Err(e) = combined.append(&video_info, video_url, &transcript_file) {
+                            warn!("Failed to add video to combined SRT: {}", e);
+                        }
                     }
                 }
-                Err(e) => {
-                    error!("Failed to get video info: {}", e);
-                }
+                Err(e) => match e.downcast_ref::<VideoUnavailable>() {
+                    Some(unavailable) => {
+                        warn!("Skipping unavailable video: {}", e);
+                        let title = entry.title.as_deref().unwrap_or(video_url);
+                        manifest.skipped(title, video_url, entry.duration, unavailable.reason.name());
+                        self.config.item_tally.add_unavailable(unavailable.reason.name());
+                    }
+                    None => {
+                        error!("Failed to get video info: {}", e);
+                        manifest.failed(video_url, video_url, None, &e);
+                    }
+                },
             }
         }
         
+        manifest.write(&channel_dir)?;
+        self.forget_video_index(url, &channel_dir);
+        
+        if filtering {
+            info!("Filters excluded {} videos", filtered_count);
+        }
+        
+        if let Some(combined) = combined {
+            let combined_file = channel_dir.join("playlist.srt");
+            fs::write(&combined_file, combined.output.trim())?;
+            info!("Combined playlist SRT saved to: {:?}", combined_file);
+        }
+        
+        if let Some(combined_transcript) = combined_transcript {
+            combined_transcript.write(&channel_dir)?;
+        }
+        
         Ok(())
     }
     
@@ -152,19 +596,17 @@ impl<'a> YouTubeProcessor<'a> {
     fn get_video_info(&self, url: &str) -> Result<VideoInfo> {
         debug!("Getting video info for: {}", url);
         
-        let output = Command::new("yt-dlp")
-            .args(&[
-                "--dump-json",
-                "--no-playlist",
-                url,
-            ])
-            .output()?;
+        let output = self.config.command_runner.output(&mut self.yt_dlp([
+            "--dump-json",
+            "--no-playlist",
+            url,
+        ]))?;
         
         if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to get video info: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
+            if let Some(reason) = Unavailability::from_stderr(&String::from_utf8_lossy(&output.stderr)) {
+                return Err(VideoUnavailable { url: url.to_string(), reason }.into());
+            }
+            return Err(self.yt_dlp_error("Failed to get video info", &output.stderr));
         }
         
         let json = String::from_utf8(output.stdout)?;
@@ -178,19 +620,14 @@ impl<'a> YouTubeProcessor<'a> {
     fn get_channel_info(&self, url: &str) -> Result<VideoInfo> {
         debug!("Getting channel info for: {}", url);
         
-        let output = Command::new("yt-dlp")
-            .args(&[
-                "--dump-json",
-                "--playlist-items", "1",
-                url,
-            ])
-            .output()?;
+        let output = self.config.command_runner.output(&mut self.yt_dlp([
+            "--dump-json",
+            "--playlist-items", "1",
+            url,
+        ]))?;
         
         if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to get channel info: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
+            return Err(self.yt_dlp_error("Failed to get channel info", &output.stderr));
         }
         
         let json = String::from_utf8(output.stdout)?;
@@ -200,56 +637,149 @@ impl<'a> YouTubeProcessor<'a> {
         Ok(video_info)
     }
     
-    /// Get list of video URLs from a channel or playlist
-    fn get_video_urls(&self, url: &str) -> Result<Vec<String>> {
-        debug!("Getting video URLs from: {}", url);
-        
-        let output = Command::new("yt-dlp")
-            .args(&[
-                "--get-id",
-                "--flat-playlist",
-                url,
-            ])
-            .output()?;
+    /// Get list of videos in a channel or playlist
+    /// 
+    /// With `index_dir`, the listing is saved there and reused instead of
+    /// crawling the channel again, until [`Self::forget_video_index`] removes it
+    /// once every video was processed or `--refresh-index` asks for a new one.
+    /// An interrupted run of a large channel thus picks up without re-crawling.
+    fn get_playlist_entries(&self, url: &str, index_dir: Option<&Path>) -> Result<Vec<PlaylistEntry>> {
+        let index_file = index_dir.map(|dir| self.video_index_file(url, dir));
+        let saved = index_file
+            .as_ref()
+            .filter(|_| !self.config.refresh_index)
+            .and_then(|file| fs::read_to_string(file).ok().map(|listing| (file, listing)));
         
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to get video URLs: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+        let listing = match saved {
+            Some((file, listing)) => {
+                info!("Using the video index saved at {:?} (pass --refresh-index to fetch it again)", file);
+                listing
+            }
+            None => {
+                let listing = self.fetch_playlist_listing(url)?;
+                if let Some(file) = &index_file {
+                    save_video_index(file, &listing);
+                }
+                listing
+            }
+        };
         
-        let ids = String::from_utf8(output.stdout)?;
-        let video_urls: Vec<String> = ids
+        let entries: Vec<PlaylistEntry> = listing
             .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|id| format!("https://www.youtube.com/watch?v={}", id.trim()))
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, '\t');
+                let id = parts.next()?.trim().to_string();
+                // yt-dlp prints "NA" for unknown fields
+                let duration = parts.next().and_then(|d| d.parse::<f64>().ok());
+                let upload_date = parts.next().and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok());
+                let title = parts.next().filter(|title| *title != "NA").map(str::to_string);
+                Some(PlaylistEntry {
+                    url: format!("https://www.youtube.com/watch?v={}", id),
+                    id,
+                    duration,
+                    upload_date,
+                    title,
+                })
+            })
             .collect();
         
-        info!("Found {} videos", video_urls.len());
-        Ok(video_urls)
+        info!("Found {} videos", entries.len());
+        Ok(entries)
+    }
+    
+    /// Where the video index of a channel or playlist URL is saved, keyed by the URL, `--video-start`/`--video-end`,
+    /// and `--order oldest`
+    fn video_index_file(&self, url: &str, dir: &Path) -> PathBuf {
+        let mut key = format!("{}#{:?}-{:?}", url, self.config.video_start, self.config.video_end);
+        if self.config.order == Order::Oldest {
+            key.push_str("#oldest");
+        }
+        dir.join(format!("video_index_{:016x}.tsv", utils::stable_hash(&key)))
+    }
+    
+    /// Remove a channel's saved video index, so the next run fetches new uploads
+    fn forget_video_index(&self, url: &str, dir: &Path) {
+        let file = self.video_index_file(url, dir);
+        if let Err(e) = fs::remove_file(&file) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove video index {:?}: {}", file, e);
+            }
+        }
     }
     
-    /// Create video directory
-    fn create_video_directory(&self, video_info: &VideoInfo) -> Result<PathBuf> {
-        let channel_name = video_info.channel.as_deref().unwrap_or("Unknown_Channel");
-        let sanitized_channel = utils::sanitize_filename(channel_name);
+    /// Flat listing of a channel or playlist from yt-dlp, one tab-separated video per line
+    fn fetch_playlist_listing(&self, url: &str) -> Result<String> {
+        debug!("Getting video list from: {}", url);
+        
+        // Flat listing is fast and still includes durations and titles for most videos
+        let mut args = vec![
+            "--flat-playlist",
+            "--print", "%(id)s\t%(duration)s\t%(upload_date)s\t%(title)s",
+        ];
         
-        let sanitized_title = utils::sanitize_filename(&video_info.title);
-        let video_dir = self.config.output_dir.join(&sanitized_channel).join(&sanitized_title);
+        // Only page through the requested part of large channels
+        let start = self.config.video_start.map(|start| start.to_string());
+        if let Some(start) = &start {
+            args.extend_from_slice(&["--playlist-start", start]);
+        }
+        let end = self.config.video_end.map(|end| end.to_string());
+        if let Some(end) = &end {
+            args.extend_from_slice(&["--playlist-end", end]);
+        }
+        if self.config.order == Order::Oldest {
+            args.push("--playlist-reverse");
+        }
         
-        debug!("Creating video directory: {:?}", video_dir);
-        fs::create_dir_all(&video_dir)?;
+        args.push(url);
+        let output = self.config.command_runner.output(&mut self.yt_dlp(args))?;
         
-        Ok(video_dir)
+        if !output.status.success() {
+            return Err(self.yt_dlp_error("Failed to get video URLs", &output.stderr));
+        }
+        
+        Ok(String::from_utf8(output.stdout)?)
+    }
+    
+    /// Get list of video IDs from a channel or playlist
+    fn get_video_ids(&self, url: &str) -> Result<Vec<String>> {
+        Ok(self
+            .get_playlist_entries(url, None)?
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect())
+    }
+    
+    /// Create video directory under `channel_dir` (or where the output template puts it)
+    /// 
+    /// If another video with the same title already has that directory,
+    /// `--on-existing` decides; `None` means skip the video.
+    fn create_video_directory(&self, video_info: &VideoInfo, channel_dir: &Path) -> Result<Option<ItemPaths>> {
+        let naming = ItemNaming {
+            channel: video_info.channel_name(),
+            title: &video_info.title,
+            id: &video_info.id,
+            date: video_info.parsed_upload_date(),
+        };
+        let paths = self.config.claim_item_paths(
+            &utils::sanitize_filename(&video_info.title),
+            &naming,
+            None,
+            |dir_name, naming| self.config.item_paths(channel_dir, dir_name, naming, "video_info.txt"),
+            |info| utils::info_field(info, "Video ID").is_some_and(|id| id != video_info.id),
+        );
+        let Some(paths) = paths else {
+            return Ok(None);
+        };
+        
+        debug!("Creating video directory: {:?}", paths.dir);
+        fs::create_dir_all(&paths.dir)?;
+        
+        Ok(Some(paths))
     }
     
     /// Create channel directory
     fn create_channel_directory(&self, channel_info: &VideoInfo) -> Result<PathBuf> {
-        let channel_name = channel_info.channel.as_deref().unwrap_or("Unknown_Channel");
-        let sanitized_channel = utils::sanitize_filename(channel_name);
-        
-        let channel_dir = self.config.output_dir.join(&sanitized_channel);
+        let channel_dir = self.config.source_dir(channel_info.channel_name());
         
         debug!("Creating channel directory: {:?}", channel_dir);
         fs::create_dir_all(&channel_dir)?;
@@ -258,9 +788,7 @@ impl<'a> YouTubeProcessor<'a> {
     }
     
     /// Save video information
-    fn save_video_info(&self, video_info: &VideoInfo, url: &str, video_dir: &Path) -> Result<()> {
-        let info_file = video_dir.join("video_info.txt");
-        
+    fn save_video_info(&self, video_info: &VideoInfo, url: &str, info_file: &Path) -> Result<()> {
         let mut info = format!("Title: {}\n", video_info.title);
         info.push_str(&format!("Video URL: {}\n", url));
         info.push_str(&format!("Video ID: {}\n", video_info.id));
@@ -277,13 +805,12 @@ impl<'a> YouTubeProcessor<'a> {
             info.push_str(&format!("Duration: {} seconds\n", duration));
         }
         
-        if let Some(description) = &video_info.description {
-            info.push_str(&format!("Description: {}\n", description));
+        if let Some(description) = video_info.description.as_deref().filter(|_| self.config.include_description) {
+            info.push_str(&format!("Description: {}\n", utils::clean_description(description)));
         }
         
-        let info_file_path = info_file.clone();
         fs::write(info_file, info)?;
-        debug!("Saved video info to: {:?}", info_file_path);
+        debug!("Saved video info to: {:?}", info_file);
         
         Ok(())
     }
@@ -297,8 +824,8 @@ impl<'a> YouTubeProcessor<'a> {
         let mut info = format!("Channel: {}\n", channel_name);
         info.push_str(&format!("Channel URL: {}\n", url));
         
-        if let Some(description) = &channel_info.description {
-            info.push_str(&format!("Description: {}\n", description));
+        if let Some(description) = channel_info.description.as_deref().filter(|_| self.config.include_description) {
+            info.push_str(&format!("Description: {}\n", utils::clean_description(description)));
         }
         
         let info_file_path = info_file.clone();
@@ -308,40 +835,288 @@ impl<'a> YouTubeProcessor<'a> {
         Ok(())
     }
     
-    /// Download and transcribe a YouTube video
-    async fn download_and_transcribe_video(&self, url: &str, video_dir: &Path) -> Result<()> {
+    /// A yt-dlp command with the configured proxy, cookies, geo-bypass, and rate limit, if any
+    /// 
+    /// `--yt-dlp-arg` values come after `args`, so they take precedence over
+    /// the tool's own options where yt-dlp lets a later option win.
+    fn yt_dlp<'b>(&self, args: impl IntoIterator<Item = &'b str>) -> Command {
+        let mut command = Command::new("yt-dlp");
+        if let Some(proxy) = &self.config.proxy {
+            command.args(["--proxy", proxy]);
+        }
+        if let Some(cookies) = &self.config.cookies {
+            command.arg("--cookies").arg(cookies);
+        }
+        if let Some(browser) = &self.config.cookies_from_browser {
+            command.args(["--cookies-from-browser", browser]);
+        }
+        if self.config.geo_bypass {
+            command.arg("--geo-bypass");
+        }
+        if let Some(rate_limit) = self.config.rate_limit {
+            command.args(["--limit-rate", &rate_limit.bytes.to_string()]);
+        }
+        command.args(args).args(&self.config.yt_dlp_args);
+        command
+    }
+    
+    /// yt-dlp format selector for a video's audio stream: the best one, or a small one with `--low-bandwidth`
+    fn audio_format(&self) -> &'static str {
+        if self.config.low_bandwidth {
+            LOW_BANDWIDTH_FORMAT
+        } else {
+            "bestaudio/best"
+        }
+    }
+    
+    /// Error for a failed yt-dlp call, with a hint when cookies or geo-restrictions are likely the problem
+    fn yt_dlp_error(&self, message: &str, stderr: &[u8]) -> anyhow::Error {
+        let stderr = String::from_utf8_lossy(stderr);
+        let uses_cookies = self.config.cookies.is_some() || self.config.cookies_from_browser.is_some();
+        
+        let hint = if uses_cookies && stderr.to_lowercase().contains("cookie") {
+            "\nyt-dlp could not use the given cookies. --cookies must be a Netscape-format cookies.txt, \
+             and --cookies-from-browser must name an installed browser you are signed in to"
+        } else if !uses_cookies && (stderr.contains("Sign in") || stderr.contains("members")) {
+            "\nThis video may be age-restricted or members-only; pass --cookies or --cookies-from-browser"
+        } else if !self.config.geo_bypass && stderr.contains("in your country") {
+            "\nThis video is geo-restricted; pass --geo-bypass, or --proxy with a server in an allowed country"
+        } else {
+            ""
+        };
+        
+        anyhow::anyhow!("{}: {}{}", message, stderr.trim_end(), hint)
+    }
+    
+    /// Detect a video's language from the start of its audio stream, without downloading all of it
+    async fn detect_video_language(&self, video_info: &VideoInfo, url: &str, info_file: &Path) -> Result<()> {
+        let output = self.config.command_runner.output(&mut self.yt_dlp([
+            "--get-url",
+            "--format", self.audio_format(),
+            "--no-playlist",
+            url,
+        ]))?;
+        
+        if !output.status.success() {
+            return Err(self.yt_dlp_error("Failed to get video audio URL", &output.stderr));
+        }
+        
+        let stream_url = String::from_utf8(output.stdout)?;
+        let stream_url = stream_url
+            .lines()
+            .next()
+            .context("yt-dlp returned no audio URL")?;
+        
+        TranscriptionService::new(self.config)
+            .report_language(&video_info.title, stream_url, info_file)
+            .await
+    }
+    
+    /// Download and transcribe a YouTube video, recording its detected language in the info file
+    async fn download_and_transcribe_video(&self, video_info: &VideoInfo, url: &str, paths: &ItemPaths) -> Result<()> {
         debug!("Downloading and transcribing video: {}", url);
         
+        // Audio with sponsor segments cut is cached apart from the full audio
+        let sponsor_categories = self
+            .config
+            .skip_sponsors
+            .iter()
+            .map(|category| category.name())
+            .collect::<Vec<_>>()
+            .join(",");
+        let cache_key = if sponsor_categories.is_empty() {
+            url.to_string()
+        } else {
+            format!("{}#sponsorblock-remove={}", url, sponsor_categories)
+        };
+        
         // Create temporary directory
         let temp_dir = tempdir()?;
-        let audio_file = temp_dir.path().join("audio.mp3");
-        
-        // Download audio using yt-dlp
-        let output = Command::new("yt-dlp")
-            .args(&[
-                "-x",
-                "--audio-format", "mp3",
-                "--audio-quality", "0",
-                "-o", audio_file.to_str().unwrap(),
-                url,
-            ])
-            .output()?;
+        let audio_file = match utils::cached_audio(self.config, &cache_key) {
+            Some(cached) => cached,
+            None => {
+                let audio_file = temp_dir.path().join("audio.mp3");
+                
+                // Download audio using yt-dlp
+                let max_filesize = self.config.max_filesize.map(|max| max.to_string());
+                let audio_quality = self.config.audio_quality.to_string();
+                let mut args = vec![
+                    "-x",
+                    "--format", self.audio_format(),
+                    "--audio-format", "mp3",
+                    "--audio-quality", &audio_quality,
+                    "-o", audio_file.to_str().unwrap(),
+                ];
+                if let Some(max_filesize) = &max_filesize {
+                    args.extend_from_slice(&["--max-filesize", max_filesize]);
+                }
+                if !sponsor_categories.is_empty() {
+                    args.extend_from_slice(&["--sponsorblock-remove", &sponsor_categories]);
+                }
+                args.push(url);
+                let output = self.config.command_runner.output(&mut self.yt_dlp(args))?;
+                
+                if !output.status.success() {
+                    return Err(self.yt_dlp_error("Failed to download video audio", &output.stderr));
+                }
+                
+                // yt-dlp skips oversized downloads without failing
+                if let (Some(max), false) = (self.config.max_filesize, audio_file.exists()) {
+                    return Err(utils::TooLarge { url: url.to_string(), max }.into());
+                }
+                
+                utils::cache_audio(self.config, &cache_key, &audio_file);
+                audio_file
+            }
+        };
         
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to download video audio: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+        utils::keep_audio(self.config, &audio_file, &paths.audio);
         
-        // Transcribe audio file
-        let transcript_file = video_dir.join("transcript.txt");
-        let transcription_service = TranscriptionService::new(self.config);
+        // Transcribe audio file, marking the video's chapters
+        let chapters = video_info.chapters();
+        if !chapters.is_empty() {
+            debug!("Video has {} chapters", chapters.len());
+        }
+        let transcription_service = TranscriptionService::new(self.config).with_chapters(chapters.clone());
         
-        transcription_service.transcribe_file(&audio_file, &transcript_file).await
+        transcription_service.transcribe_file(&audio_file, &paths.transcript, &video_info.title, url).await
             .context("Failed to transcribe video audio")?;
+        embed::embed_transcript(self.config, &paths.audio, &transcription_service.transcript_path(&paths.transcript), &chapters);
+        transcription_service.record_detection(&paths.info, &video_info.title)?;
         
         info!("Successfully transcribed video: {}", url);
         Ok(())
     }
 }
+
+/// Save a fetched video index
+/// 
+/// The index is written under a temporary name and renamed into place, so an
+/// interrupted write never leaves a partial index to be reused. Failing to
+/// save only logs a warning.
+fn save_video_index(file: &Path, listing: &str) {
+    let partial = file.with_extension("tsv.partial");
+    let result = fs::write(&partial, listing).and_then(|_| fs::rename(&partial, file));
+    match result {
+        Ok(()) => debug!("Saved video index to {:?}", file),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            warn!("Failed to save video index to {:?}: {}", file, e);
+        }
+    }
+}
+
+/// A video listed in a channel or playlist
+#[derive(Debug, Clone)]
+struct PlaylistEntry {
+    id: String,
+    url: String,
+    duration: Option<f64>,
+    /// Often missing from flat listings
+    upload_date: Option<NaiveDate>,
+    title: Option<String>,
+}
+
+impl PlaylistEntry {
+    /// The video as shown by `list` and `--interactive`
+    fn listed(&self, source: &str) -> ListedItem {
+        ListedItem {
+            source: source.to_string(),
+            title: self.title.clone().unwrap_or_else(|| self.id.clone()),
+            date: self.upload_date,
+            duration: self.duration,
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// A single SRT spanning several videos played back-to-back
+#[derive(Default)]
+struct CombinedSrt {
+    /// Rendered SRT content so far
+    output: String,
+    /// Number of cues written so far
+    cue_count: usize,
+    /// Start offset of the next video in milliseconds
+    offset_ms: u64,
+    /// Number of videos added so far
+    video_count: usize,
+}
+
+impl CombinedSrt {
+    /// Append a video's SRT, shifted to start where the previous video ended
+    fn append(&mut self, video_info: &VideoInfo, url: &str, transcript_file: &Path) -> Result<()> {
+        let mut cues = srt::parse(&fs::read_to_string(transcript_file)?)?;
+        
+        // Prefer the real video length; fall back to the last cue if it's unknown
+        let duration_ms = video_info
+            .duration
+            .map(|secs| (secs * 1000.0).round() as u64)
+            .or_else(|| cues.last().map(|cue| cue.end_ms))
+            .unwrap_or(0);
+        
+        srt::shift(&mut cues, self.offset_ms);
+        
+        self.video_count += 1;
+        self.output.push_str(&format!(
+            "# Video {}: {} ({}) starts at {}\n\n",
+            self.video_count,
+            video_info.title,
+            url,
+            srt::format_timestamp(self.offset_ms)
+        ));
+        self.output.push_str(&srt::render_from(&cues, self.cue_count + 1));
+        
+        self.cue_count += cues.len();
+        self.offset_ms += duration_ms;
+        Ok(())
+    }
+}
+
+/// Fail with an installation hint if yt-dlp is not available
+fn ensure_yt_dlp() -> Result<()> {
+    if !utils::check_command("yt-dlp") {
+        return Err(anyhow::anyhow!(
+            "yt-dlp is not installed. Please install it with 'brew install yt-dlp' or visit https://github.com/yt-dlp/yt-dlp"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ConfigFile, FileSize};
+    use crate::runner::RecordingRunner;
+    use std::path::PathBuf;
+    
+    #[test]
+    fn yt_dlp_puts_network_options_before_the_call_and_extra_args_last() {
+        let runner = RecordingRunner::new(r#"{"id": "abc123", "title": "A video"}"#);
+        let mut config = Config::without_transcription(None, None, None, None, &ConfigFile::default()).unwrap();
+        config.command_runner = Box::new(runner.clone());
+        config.proxy = Some("socks5://127.0.0.1:1080".to_string());
+        config.cookies = Some(PathBuf::from("cookies.txt"));
+        config.cookies_from_browser = Some("firefox".to_string());
+        config.geo_bypass = true;
+        config.rate_limit = Some(FileSize { bytes: 500_000 });
+        config.yt_dlp_args = vec!["--extractor-args".to_string(), "youtube:player_client=web".to_string()];
+        
+        let video_info = YouTubeProcessor::new(&config).get_video_info("https://youtu.be/abc123").unwrap();
+        assert_eq!(video_info.id, "abc123");
+        
+        assert_eq!(
+            runner.commands(),
+            vec![vec![
+                "yt-dlp",
+                "--proxy", "socks5://127.0.0.1:1080",
+                "--cookies", "cookies.txt",
+                "--cookies-from-browser", "firefox",
+                "--geo-bypass",
+                "--limit-rate", "500000",
+                "--dump-json", "--no-playlist", "https://youtu.be/abc123",
+                "--extractor-args", "youtube:player_client=web",
+            ]]
+        );
+    }
+}